@@ -0,0 +1,142 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::PathBuf};
+
+use common::{input, Answer};
+use serde::{Deserialize, Serialize};
+
+/// Confirmed answers for one day, keyed by input profile. Stored in `solutions/dayNN/answers.toml`:
+///
+/// ```toml
+/// [sample]
+/// part1 = 13140
+/// part2 = """
+/// ##..##..
+/// ###...##"""
+/// ```
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, ProfileAnswers>);
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ProfileAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Expected>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Expected>,
+}
+
+/// TOML has no 128 bit integers, so numbers are stored as `i64` and text as strings.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Expected {
+    Number(i64),
+    Text(String),
+}
+
+impl From<&Expected> for Answer {
+    fn from(expected: &Expected) -> Self {
+        match expected {
+            Expected::Number(n) => Answer::Number(*n as i128),
+            // multi-line strings usually end with a newline before the closing quotes
+            Expected::Text(s) => Answer::Text(s.trim_end_matches('\n').to_string()),
+        }
+    }
+}
+
+impl Expected {
+    /// Parses an answer typed on the command line.
+    pub fn parse(answer: &str) -> Self {
+        match answer.parse() {
+            Ok(n) => Expected::Number(n),
+            Err(_) => Expected::Text(answer.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Toml {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "Could not read {}: {}", path.display(), source)
+            }
+            AnswersError::Toml { path, source } => {
+                write!(f, "Invalid answers in {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+pub fn path(day: u8) -> PathBuf {
+    input::day_dir(day).join("answers.toml")
+}
+
+impl Answers {
+    /// Loads the answers recorded for the day. A day without an `answers.toml` has no answers.
+    pub fn load(day: u8) -> Result<Self, AnswersError> {
+        let path = path(day);
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                toml::from_str(&contents).map_err(|source| AnswersError::Toml { path, source })
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(AnswersError::Io { path, source }),
+        }
+    }
+
+    pub fn get(&self, profile: &str, part: u8) -> Option<&Expected> {
+        let answers = self.0.get(profile)?;
+        match part {
+            1 => answers.part1.as_ref(),
+            2 => answers.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn insert(&mut self, profile: &str, answers: ProfileAnswers) {
+        self.0.insert(profile.to_string(), answers);
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("answers are always representable in TOML")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers: Answers = toml::from_str(
+            "[sample]\npart1 = 13140\npart2 = \"\"\"\n##..\n###.\n\"\"\"\n\n[real]\npart1 = \"CMZ\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            Answer::from(answers.get("sample", 1).unwrap()),
+            Answer::Number(13140)
+        );
+        assert_eq!(
+            Answer::from(answers.get("sample", 2).unwrap()),
+            Answer::Text("##..\n###.".to_string())
+        );
+        assert_eq!(
+            Answer::from(answers.get("real", 1).unwrap()),
+            Answer::from("CMZ")
+        );
+        assert!(answers.get("real", 2).is_none());
+        assert!(answers.get("alt-account", 1).is_none());
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    fmt::{Display, Write},
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use clap::Args;
+use common::{InputArgs, InputError, ParseError, Solution};
+use serde::{Deserialize, Serialize};
+
+use crate::registry::Day;
+
+/// Arguments for `aoc bench`.
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Only time this part of the puzzle (parsing is always timed)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+    /// Number of times to run each stage
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+    /// Input profile to benchmark
+    #[arg(long, default_value = "real")]
+    pub profile: String,
+    /// Write the report as JSON to this file, which can later be used as a baseline
+    #[arg(long)]
+    pub json: Option<PathBuf>,
+    /// Write the report as a Markdown table to this file
+    #[arg(long)]
+    pub markdown: Option<PathBuf>,
+    /// JSON report of an earlier run to compare against
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+    /// How many percent slower than the baseline a stage may get before it counts as a
+    /// regression
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+}
+
+/// Durations of every run of one stage of a day.
+pub struct Samples {
+    pub stage: Stage,
+    pub runs: Vec<Duration>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    fn part(part: u8) -> Self {
+        match part {
+            1 => Stage::Part1,
+            _ => Stage::Part2,
+        }
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stage = match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part 1",
+            Stage::Part2 => "part 2",
+        };
+        f.pad(stage)
+    }
+}
+
+/// Times parsing and then each part on the already parsed input, so the parts don't include the
+/// cost of parsing.
+pub fn measure<S: Solution>(
+    profile: &str,
+    input: &str,
+    part: Option<u8>,
+    runs: u32,
+) -> Result<Vec<Samples>, ParseError> {
+    let solution = S::for_profile(profile);
+    let parsed = solution.parse_input(input)?;
+
+    let mut parse = Vec::new();
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = std::hint::black_box(solution.parse(input));
+        parse.push(start.elapsed());
+        drop(parsed);
+    }
+    let mut samples = vec![Samples {
+        stage: Stage::Parse,
+        runs: parse,
+    }];
+
+    for p in (1..=S::PARTS).filter(|&p| part.is_none_or(|part| part == p)) {
+        let mut runs_of_part = Vec::new();
+        for _ in 0..runs {
+            let start = Instant::now();
+            std::hint::black_box(solution.solve_parsed(p, &parsed)?);
+            runs_of_part.push(start.elapsed());
+        }
+        samples.push(Samples {
+            stage: Stage::part(p),
+            runs: runs_of_part,
+        });
+    }
+    Ok(samples)
+}
+
+/// Summary statistics of one stage, in nanoseconds.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Entry {
+    pub day: u8,
+    pub stage: Stage,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Entry {
+    fn new(day: u8, samples: &Samples) -> Self {
+        let mut runs: Vec<u64> = samples.runs.iter().map(|d| d.as_nanos() as u64).collect();
+        runs.sort_unstable();
+        let n = runs.len() as f64;
+        let mean = runs.iter().sum::<u64>() as f64 / n;
+        let variance = runs.iter().map(|&r| (r as f64 - mean).powi(2)).sum::<f64>() / n;
+        let mid = runs.len() / 2;
+        let median = if runs.len().is_multiple_of(2) {
+            (runs[mid - 1] + runs[mid]) / 2
+        } else {
+            runs[mid]
+        };
+        Self {
+            day,
+            stage: samples.stage,
+            median_ns: median,
+            mean_ns: mean as u64,
+            stddev_ns: variance.sqrt() as u64,
+            min_ns: runs[0],
+            max_ns: runs[runs.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Report {
+    pub profile: String,
+    pub runs: u32,
+    pub entries: Vec<Entry>,
+}
+
+impl Report {
+    fn baseline_for(&self, entry: &Entry) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|e| e.day == entry.day && e.stage == entry.stage)
+    }
+}
+
+#[derive(Debug)]
+pub enum BenchError {
+    Input(InputError),
+    /// A parse error, already rendered as a diagnostic.
+    Parse(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+impl Display for BenchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchError::Input(e) => write!(f, "{}", e),
+            BenchError::Parse(diagnostic) => write!(f, "{}", diagnostic),
+            BenchError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            BenchError::Json { path, source } => {
+                write!(
+                    f,
+                    "Invalid benchmark report in {}: {}",
+                    path.display(),
+                    source
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BenchError {}
+
+fn format_duration(ns: u64) -> String {
+    let ns = ns as f64;
+    if ns >= 1e9 {
+        format!("{:.2} s", ns / 1e9)
+    } else if ns >= 1e6 {
+        format!("{:.2} ms", ns / 1e6)
+    } else if ns >= 1e3 {
+        format!("{:.1} µs", ns / 1e3)
+    } else {
+        format!("{} ns", ns)
+    }
+}
+
+/// Percentage the median changed by compared to the baseline, positive when slower.
+fn change(entry: &Entry, baseline: &Entry) -> f64 {
+    (entry.median_ns as f64 - baseline.median_ns as f64) / baseline.median_ns.max(1) as f64 * 100.0
+}
+
+fn markdown(report: &Report, baseline: Option<&Report>, threshold: f64) -> String {
+    let mut table = String::new();
+    table.push_str("| Day | Stage | Median | Mean ± σ | Min | Max |");
+    if baseline.is_some() {
+        table.push_str(" Baseline | Change |");
+    }
+    table.push('\n');
+    table.push_str("| --: | :-- | --: | --: | --: | --: |");
+    if baseline.is_some() {
+        table.push_str(" --: | --: |");
+    }
+    table.push('\n');
+
+    for entry in &report.entries {
+        write!(
+            table,
+            "| {} | {} | {} | {} ± {} | {} | {} |",
+            entry.day,
+            entry.stage,
+            format_duration(entry.median_ns),
+            format_duration(entry.mean_ns),
+            format_duration(entry.stddev_ns),
+            format_duration(entry.min_ns),
+            format_duration(entry.max_ns),
+        )
+        .unwrap();
+        if let Some(baseline) = baseline {
+            match baseline.baseline_for(entry) {
+                Some(old) => {
+                    let change = change(entry, old);
+                    let flag = if change > threshold { " ⚠" } else { "" };
+                    write!(
+                        table,
+                        " {} | {:+.1}%{} |",
+                        format_duration(old.median_ns),
+                        change,
+                        flag
+                    )
+                    .unwrap();
+                }
+                None => table.push_str(" - | - |"),
+            }
+        }
+        table.push('\n');
+    }
+    table
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), BenchError> {
+    fs::write(path, contents).map_err(|source| BenchError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn load_baseline(path: &Path) -> Result<Report, BenchError> {
+    let contents = fs::read_to_string(path).map_err(|source| BenchError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_str(&contents).map_err(|source| BenchError::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Benchmarks the given days, prints a Markdown table and writes the requested reports. Returns
+/// whether every stage stayed within the threshold of the baseline.
+pub fn bench(days: &[&Day], args: &BenchArgs) -> Result<bool, BenchError> {
+    // read the baseline first so a typo doesn't throw away a long benchmark run
+    let baseline = args.baseline.as_deref().map(load_baseline).transpose()?;
+
+    let input_args = InputArgs::profile(&args.profile);
+    let mut report = Report {
+        profile: args.profile.clone(),
+        runs: args.runs,
+        entries: Vec::new(),
+    };
+    for day in days {
+        let input = input_args.load(day.day).map_err(BenchError::Input)?;
+        eprintln!("Benchmarking day {}", day.day);
+        let samples = day
+            .bench(&args.profile, &input, args.part, args.runs)
+            .map_err(|e| BenchError::Parse(e.diagnostic(&input, &input_args.source(day.day))))?;
+        for samples in samples {
+            report.entries.push(Entry::new(day.day, &samples));
+        }
+    }
+
+    let table = markdown(&report, baseline.as_ref(), args.threshold);
+    print!("{}", table);
+    if let Some(path) = &args.markdown {
+        write_file(path, &table)?;
+    }
+    if let Some(path) = &args.json {
+        let json = serde_json::to_string_pretty(&report).expect("reports serialize to JSON");
+        write_file(path, &json)?;
+    }
+
+    let regressed = baseline.is_some_and(|baseline| {
+        report.entries.iter().any(|entry| {
+            baseline
+                .baseline_for(entry)
+                .is_some_and(|old| change(entry, old) > args.threshold)
+        })
+    });
+    Ok(!regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(stage: Stage, median_ns: u64) -> Entry {
+        Entry {
+            day: 6,
+            stage,
+            median_ns,
+            mean_ns: median_ns,
+            stddev_ns: 0,
+            min_ns: median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        let samples = Samples {
+            stage: Stage::Part1,
+            runs: [4, 1, 3, 2].map(Duration::from_micros).to_vec(),
+        };
+        let entry = Entry::new(6, &samples);
+        assert_eq!(entry.median_ns, 2_500);
+        assert_eq!(entry.mean_ns, 2_500);
+        assert_eq!(entry.stddev_ns, 1_118);
+        assert_eq!(entry.min_ns, 1_000);
+        assert_eq!(entry.max_ns, 4_000);
+    }
+
+    #[test]
+    fn test_baseline_comparison() {
+        let baseline = Report {
+            profile: "real".to_string(),
+            runs: 10,
+            entries: vec![entry(Stage::Parse, 1_000), entry(Stage::Part1, 2_300_000)],
+        };
+        let report = Report {
+            profile: "real".to_string(),
+            runs: 10,
+            entries: vec![
+                entry(Stage::Parse, 1_050),
+                entry(Stage::Part1, 3_400_000),
+                entry(Stage::Part2, 10),
+            ],
+        };
+        let table = markdown(&report, Some(&baseline), 10.0);
+        let rows: Vec<&str> = table.lines().skip(2).collect();
+        assert_eq!(
+            rows,
+            [
+                "| 6 | parse | 1.1 µs | 1.1 µs ± 0 ns | 1.1 µs | 1.1 µs | 1.0 µs | +5.0% |",
+                "| 6 | part 1 | 3.40 ms | 3.40 ms ± 0 ns | 3.40 ms | 3.40 ms | 2.30 ms | +47.8% ⚠ |",
+                "| 6 | part 2 | 10 ns | 10 ns ± 0 ns | 10 ns | 10 ns | - | - |",
+            ]
+        );
+    }
+}
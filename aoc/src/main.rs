@@ -0,0 +1,264 @@
+mod answers;
+mod bench;
+mod registry;
+mod scaffold;
+mod snafu;
+mod verify;
+
+use std::{fs, path::PathBuf, process, str::FromStr};
+
+use clap::{Parser, Subcommand};
+use common::{Explanation, InputArgs, ParseError};
+use registry::Day;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for a single day, or `all` of them
+    Run {
+        day: DaySelection,
+        /// Only run this part of the puzzle
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Also show how each part found its answer, for days that can, like the route taken
+        #[arg(long)]
+        explain: bool,
+        /// Write how each part found its answer to `dayNN-partN.txt` in this directory instead,
+        /// and to `dayNN-partN.json` for days that have it as JSON
+        #[arg(long, value_name = "DIR")]
+        explain_to: Option<PathBuf>,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Time parsing and solving each part, optionally comparing with an earlier run
+    Bench {
+        #[arg(default_value = "all")]
+        day: DaySelection,
+        #[command(flatten)]
+        args: bench::BenchArgs,
+    },
+    /// Check every day's answers against its answers.toml
+    Verify {
+        #[arg(default_value = "all")]
+        day: DaySelection,
+        /// Input profiles to check
+        #[arg(long = "profile", default_values = ["sample", "real"])]
+        profiles: Vec<String>,
+    },
+    /// Create the crate for a new day and register it with the runner
+    New(scaffold::NewArgs),
+    /// Calculate with day 25's SNAFU numbers
+    Snafu(snafu::SnafuArgs),
+}
+
+#[derive(Clone, Copy)]
+enum DaySelection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Self::All);
+        }
+        match s.parse::<u8>() {
+            Ok(day) if registry::get(day).is_some() => Ok(Self::Day(day)),
+            _ => Err(format!(
+                "expected a day between 1 and 25 or `all`, got {}",
+                s
+            )),
+        }
+    }
+}
+
+impl DaySelection {
+    fn days(self) -> Vec<&'static Day> {
+        match self {
+            DaySelection::All => registry::DAYS.iter().collect(),
+            DaySelection::Day(d) => registry::get(d).into_iter().collect(),
+        }
+    }
+
+    /// The selected days that have `part`, if only one part is asked for. Asking a single day
+    /// for a part it doesn't have is an error, ready to print.
+    fn days_with_part(self, part: Option<u8>) -> Result<Vec<&'static Day>, String> {
+        let days = self.days();
+        let Some(part) = part else {
+            return Ok(days);
+        };
+        if let (DaySelection::Day(_), [day]) = (self, &days[..]) {
+            if part > day.parts {
+                let parts = match day.parts {
+                    1 => "part 1".to_string(),
+                    n => format!("parts 1 to {}", n),
+                };
+                return Err(format!(
+                    "day {} has no part {}, only {}",
+                    day.day, part, parts
+                ));
+            }
+        }
+        Ok(days.into_iter().filter(|day| part <= day.parts).collect())
+    }
+}
+
+/// Where `aoc run` shows how each part found its answer.
+enum Explain {
+    Print,
+    Write(PathBuf),
+}
+
+impl Explain {
+    fn show(&self, day: u8, part: u8, explanation: Explanation) -> Result<(), String> {
+        let Explain::Write(dir) = self else {
+            println!("{}", explanation.text);
+            return Ok(());
+        };
+        let name = format!("day{:02}-part{}", day, part);
+        let write = |extension: &str, contents: &str| {
+            let path = dir.join(&name).with_extension(extension);
+            fs::write(&path, contents).map_err(|e| format!("{}: {}", path.display(), e))
+        };
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        write("txt", &(explanation.text + "\n"))?;
+        if let Some(json) = explanation.json {
+            write("json", &json)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the selected parts of a day. Errors are returned ready to print: a parse error is rendered
+/// as a diagnostic pointing into the input.
+fn run_day(
+    day: &Day,
+    part: Option<u8>,
+    explain: Option<&Explain>,
+    args: &InputArgs,
+) -> Result<(), String> {
+    let input = args.load(day.day).map_err(|e| e.to_string())?;
+
+    println!("Day {}", day.day);
+    for p in 1..=day.parts {
+        if part.is_none_or(|part| part == p) {
+            let diagnostic = |e: ParseError| e.diagnostic(&input, &args.source(day.day));
+            let answer = day.solve(&args.profile, p, &input).map_err(diagnostic)?;
+            common::print_answer(p, &answer);
+            if let Some(explain) = explain {
+                if let Some(explanation) =
+                    day.explain(&args.profile, p, &input).map_err(diagnostic)?
+                {
+                    explain.show(day.day, p, explanation)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    common::init_tracing();
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            explain,
+            explain_to,
+            input,
+        } => {
+            if matches!(day, DaySelection::All) && input.input.is_some() {
+                eprintln!("--input can only be used when running a single day");
+                process::exit(2);
+            }
+
+            let explain = match explain_to {
+                Some(dir) => Some(Explain::Write(dir)),
+                None => explain.then_some(Explain::Print),
+            };
+
+            let days = day.days_with_part(part).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(2);
+            });
+
+            // keep going when a day's input is missing so `run all` reports every gap
+            let mut failed = false;
+            for day in days {
+                if let Err(e) = run_day(day, part, explain.as_ref(), &input) {
+                    eprintln!("{}", e);
+                    failed = true;
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
+        Command::Bench { day, args } => {
+            let days = day.days_with_part(args.part).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(2);
+            });
+            match bench::bench(&days, &args) {
+                Ok(true) => {}
+                Ok(false) => {
+                    eprintln!("Some stages regressed by more than {}%", args.threshold);
+                    process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Verify { day, profiles } => {
+            if !verify::verify(&day.days(), &profiles) {
+                process::exit(1);
+            }
+        }
+        Command::New(args) => {
+            if let Err(e) = scaffold::new_day(&args) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        Command::Snafu(args) => {
+            if let Err(e) = snafu::snafu(&args) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_with_part() {
+        let days = |selection: DaySelection, part| {
+            selection
+                .days_with_part(part)
+                .map(|days| days.iter().map(|day| day.day).collect::<Vec<_>>())
+        };
+        assert_eq!(days(DaySelection::Day(25), Some(1)), Ok(vec![25]));
+        assert_eq!(
+            days(DaySelection::Day(25), Some(2)),
+            Err("day 25 has no part 2, only part 1".to_string())
+        );
+        assert_eq!(days(DaySelection::Day(14), Some(2)), Ok(vec![14]));
+        // running every day's second part leaves out day 25
+        assert_eq!(days(DaySelection::All, Some(2)), Ok((1..=24).collect()));
+        assert_eq!(days(DaySelection::All, None).map(|d| d.len()), Ok(25));
+    }
+}
@@ -0,0 +1,97 @@
+use common::{Answer, Explanation, ParseError, Solution};
+
+use crate::bench::{self, Samples};
+
+/// Signature of [`bench::measure`] for one day.
+type Measure = fn(&str, &str, Option<u8>, u32) -> Result<Vec<Samples>, ParseError>;
+
+pub struct Day {
+    pub day: u8,
+    pub parts: u8,
+    solve: fn(&str, u8, &str) -> Result<Answer, ParseError>,
+    explain: fn(&str, u8, &str) -> Result<Option<Explanation>, ParseError>,
+    bench: Measure,
+}
+
+impl Day {
+    const fn new<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            parts: S::PARTS,
+            solve: solve::<S>,
+            explain: explain::<S>,
+            bench: bench::measure::<S>,
+        }
+    }
+
+    /// Solves one part of the puzzle using the constants for the input profile.
+    pub fn solve(&self, profile: &str, part: u8, input: &str) -> Result<Answer, ParseError> {
+        (self.solve)(profile, part, input)
+    }
+
+    /// Shows how one part of the puzzle found its answer, if the day can.
+    pub fn explain(
+        &self,
+        profile: &str,
+        part: u8,
+        input: &str,
+    ) -> Result<Option<Explanation>, ParseError> {
+        (self.explain)(profile, part, input)
+    }
+
+    /// Times parsing and solving each part (or only `part`) `runs` times.
+    pub fn bench(
+        &self,
+        profile: &str,
+        input: &str,
+        part: Option<u8>,
+        runs: u32,
+    ) -> Result<Vec<Samples>, ParseError> {
+        (self.bench)(profile, input, part, runs)
+    }
+}
+
+fn solve<S: Solution>(profile: &str, part: u8, input: &str) -> Result<Answer, ParseError> {
+    S::for_profile(profile).solve(part, input)
+}
+
+fn explain<S: Solution>(
+    profile: &str,
+    part: u8,
+    input: &str,
+) -> Result<Option<Explanation>, ParseError> {
+    let solution = S::for_profile(profile);
+    Ok(solution.explain(part, &solution.parse_input(input)?))
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(),
+    Day::new::<day02::Day02>(),
+    Day::new::<day03::Day03>(),
+    Day::new::<day04::Day04>(),
+    Day::new::<day05::Day05>(),
+    Day::new::<day06::Day06>(),
+    Day::new::<day07::Day07>(),
+    Day::new::<day08::Day08>(),
+    Day::new::<day09::Day09>(),
+    Day::new::<day10::Day10>(),
+    Day::new::<day11::Day11>(),
+    Day::new::<day12::Day12>(),
+    Day::new::<day13::Day13>(),
+    Day::new::<day14::Day14>(),
+    Day::new::<day15::Day15>(),
+    Day::new::<day16::Day16>(),
+    Day::new::<day17::Day17>(),
+    Day::new::<day18::Day18>(),
+    Day::new::<day19::Day19>(),
+    Day::new::<day20::Day20>(),
+    Day::new::<day21::Day21>(),
+    Day::new::<day22::Day22>(),
+    Day::new::<day23::Day23>(),
+    Day::new::<day24::Day24>(),
+    Day::new::<day25::Day25>(),
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,321 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use clap::Args;
+use common::input::SAMPLE_PROFILE;
+
+use crate::answers::{self, Answers, Expected, ProfileAnswers};
+
+const LIB_TEMPLATE: &str = include_str!("../../template.rs");
+
+/// Arguments for creating the crate of a new day.
+#[derive(Args)]
+pub struct NewArgs {
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Year to download the puzzle input from
+    #[arg(long, default_value_t = 2022)]
+    year: u16,
+    /// File containing the sample input, read from stdin when omitted
+    #[arg(long)]
+    sample: Option<PathBuf>,
+    /// Expected answer to part 1 for the sample input, prompted for when omitted
+    #[arg(long)]
+    part1: Option<String>,
+    /// Expected answer to part 2 for the sample input, prompted for when omitted
+    #[arg(long)]
+    part2: Option<String>,
+    /// Copy the puzzle input from this file instead of downloading it with $AOC_SESSION
+    #[arg(long)]
+    offline: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum NewDayError {
+    Exists(PathBuf),
+    Io { path: PathBuf, source: io::Error },
+    NoSession,
+    Download(String),
+    Register(PathBuf),
+}
+
+impl Display for NewDayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NewDayError::Exists(path) => {
+                write!(
+                    f,
+                    "{} already exists, refusing to overwrite it",
+                    path.display()
+                )
+            }
+            NewDayError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            NewDayError::NoSession => write!(
+                f,
+                "Set AOC_SESSION to download the puzzle input or pass --offline <file>"
+            ),
+            NewDayError::Download(err) => write!(f, "Could not download the puzzle input: {}", err),
+            NewDayError::Register(path) => write!(
+                f,
+                "Could not find where to register the new day in {}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NewDayError {}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> NewDayError + '_ {
+    |source| NewDayError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+fn read(path: &Path) -> Result<String, NewDayError> {
+    fs::read_to_string(path).map_err(io_error(path))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), NewDayError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error(parent))?;
+    }
+    fs::write(path, contents).map_err(io_error(path))
+}
+
+fn workspace_root() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
+}
+
+/// Creates `solutions/dayNN` and adds it to the workspace and the runner.
+///
+/// Everything is gathered before the first file is written, so a failed download or a typo in a
+/// path doesn't leave a half created day behind.
+pub fn new_day(args: &NewArgs) -> Result<(), NewDayError> {
+    let root = workspace_root();
+    let name = format!("day{:02}", args.day);
+    let dir = root.join("solutions").join(&name);
+    if dir.exists() {
+        return Err(NewDayError::Exists(dir));
+    }
+
+    let sample = match &args.sample {
+        Some(path) => read(path)?,
+        None => {
+            eprintln!("Paste the sample input, then press Ctrl-D:");
+            let mut sample = String::new();
+            io::stdin()
+                .read_to_string(&mut sample)
+                .map_err(io_error(Path::new("stdin")))?;
+            sample
+        }
+    };
+    let part1 = answer_or_prompt(&args.part1, 1)?;
+    let part2 = answer_or_prompt(&args.part2, 2)?;
+    let input = match &args.offline {
+        Some(path) => read(path)?,
+        None => download(args.year, args.day)?,
+    };
+
+    write(&dir.join("Cargo.toml"), &cargo_toml(&name))?;
+    write(
+        &dir.join("src/lib.rs"),
+        &render_lib(args.day, &part1, &part2),
+    )?;
+    write(&dir.join("src/main.rs"), &render_main(args.day))?;
+    write(&dir.join("data/sample.txt"), &sample)?;
+    write(&answers::path(args.day), &sample_answers(&part1, &part2))?;
+    write(&dir.join("data/input.txt"), &input)?;
+    register(&root, args.day)?;
+
+    println!("Created {}", dir.display());
+    println!(
+        "Try it with `cargo run -p aoc -- run {} --profile sample`",
+        args.day
+    );
+    Ok(())
+}
+
+fn answer_or_prompt(answer: &Option<String>, part: u8) -> Result<String, NewDayError> {
+    if let Some(answer) = answer {
+        return Ok(answer.clone());
+    }
+    eprint!("Sample answer for part {} (leave empty if unknown): ", part);
+    io::stderr().flush().ok();
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(io_error(Path::new("stdin")))?;
+    Ok(answer.trim().to_string())
+}
+
+fn download(year: u16, day: u8) -> Result<String, NewDayError> {
+    let session = env::var("AOC_SESSION").map_err(|_| NewDayError::NoSession)?;
+    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--cookie"])
+        .arg(format!("session={}", session))
+        .arg(&url)
+        .output()
+        .map_err(|e| NewDayError::Download(format!("could not run curl: {}", e)))?;
+    if !output.status.success() {
+        return Err(NewDayError::Download(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| NewDayError::Download(e.to_string()))
+}
+
+fn cargo_toml(name: &str) -> String {
+    format!(
+        r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+common = {{ path = "../../common" }}
+"#,
+        name
+    )
+}
+
+/// Return type and literal used for an expected answer in the generated tests. Unknown answers
+/// become `0` so the test fails until it is filled in.
+fn answer_literal(answer: &str) -> (&'static str, String) {
+    if answer.is_empty() {
+        ("u32", "0".to_string())
+    } else if answer.parse::<u32>().is_ok() {
+        ("u32", answer.to_string())
+    } else if answer.parse::<u64>().is_ok() {
+        ("u64", answer.to_string())
+    } else if answer.parse::<i64>().is_ok() {
+        ("i64", answer.to_string())
+    } else {
+        ("String", format!("{:?}", answer))
+    }
+}
+
+fn render_lib(day: u8, part1: &str, part2: &str) -> String {
+    let (part1_type, part1) = answer_literal(part1);
+    let (part2_type, part2) = answer_literal(part2);
+    LIB_TEMPLATE
+        .replace("DAY_PADDED", &format!("{:02}", day))
+        .replace("DAY_NUM", &day.to_string())
+        .replace("PART1_TYPE", part1_type)
+        .replace("PART2_TYPE", part2_type)
+        .replace("PART1_SAMPLE_SOLUTION", &part1)
+        .replace("PART2_SAMPLE_SOLUTION", &part2)
+}
+
+/// `answers.toml` with the sample answers that are known so far.
+fn sample_answers(part1: &str, part2: &str) -> String {
+    let known = |answer: &str| (!answer.is_empty()).then(|| Expected::parse(answer));
+    let mut answers = Answers::default();
+    answers.insert(
+        SAMPLE_PROFILE,
+        ProfileAnswers {
+            part1: known(part1),
+            part2: known(part2),
+        },
+    );
+    answers.to_toml()
+}
+
+fn render_main(day: u8) -> String {
+    format!(
+        "use day{0:02}::Day{0:02};\n\nfn main() {{\n    common::main::<Day{0:02}>();\n}}\n",
+        day
+    )
+}
+
+/// Adds the day to the workspace members (unless a glob already covers it), to the runner's
+/// dependencies and to its registry.
+fn register(root: &Path, day: u8) -> Result<(), NewDayError> {
+    let name = format!("day{:02}", day);
+
+    let workspace = root.join("Cargo.toml");
+    let manifest = read(&workspace)?;
+    if !manifest.contains("\"solutions/*\"") {
+        let member = format!("\"solutions/{}\"", name);
+        let manifest = manifest.replacen("members = [", &format!("members = [{}, ", member), 1);
+        write(&workspace, &manifest)?;
+    }
+
+    let aoc = root.join("aoc/Cargo.toml");
+    let dependency = format!("{0} = {{ path = \"../solutions/{0}\" }}", name);
+    let manifest = insert_sorted(&read(&aoc)?, "day", &dependency)
+        .ok_or_else(|| NewDayError::Register(aoc.clone()))?;
+    write(&aoc, &manifest)?;
+
+    let registry = root.join("aoc/src/registry.rs");
+    let entry = format!("    Day::new::<{0}::Day{1:02}>(),", name, day);
+    let source = insert_sorted(&read(&registry)?, "Day::new::<day", &entry)
+        .ok_or_else(|| NewDayError::Register(registry.clone()))?;
+    write(&registry, &source)
+}
+
+/// Inserts `line` into the run of lines starting with `prefix`, keeping the run sorted. Returns
+/// `None` if there are no such lines.
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Option<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let matching: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].trim_start().starts_with(prefix))
+        .collect();
+    let first = *matching.first()?;
+    let position = matching
+        .iter()
+        .rfind(|&&i| lines[i].trim_start() < line.trim_start())
+        .map_or(first, |&i| i + 1);
+    lines.insert(position, line);
+    Some(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_lib() {
+        let lib = render_lib(7, "95437", "ABC");
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(lib.contains("pub fn problem1(input: &str) -> u32"));
+        assert!(lib.contains("pub fn problem2(input: &str) -> String"));
+        assert!(lib.contains("assert_eq!(res, 95437);"));
+        assert!(lib.contains("assert_eq!(res, \"ABC\");"));
+    }
+
+    #[test]
+    fn test_sample_answers() {
+        assert_eq!(sample_answers("95437", ""), "[sample]\npart1 = 95437\n");
+        assert_eq!(
+            sample_answers("CMZ", "MCD"),
+            "[sample]\npart1 = \"CMZ\"\npart2 = \"MCD\"\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let manifest = "[dependencies]\nclap = \"4\"\nday01 = 1\nday03 = 3\n";
+        assert_eq!(
+            insert_sorted(manifest, "day", "day02 = 2").unwrap(),
+            "[dependencies]\nclap = \"4\"\nday01 = 1\nday02 = 2\nday03 = 3\n"
+        );
+        assert_eq!(
+            insert_sorted(manifest, "day", "day26 = 26").unwrap(),
+            "[dependencies]\nclap = \"4\"\nday01 = 1\nday03 = 3\nday26 = 26\n"
+        );
+        assert_eq!(insert_sorted("clap = \"4\"\n", "day", "day01 = 1"), None);
+    }
+}
@@ -0,0 +1,198 @@
+use std::io::{self, Read};
+
+use clap::Args;
+use common::ParseError;
+use day25::snafu::Snafu;
+use num_bigint::BigInt;
+
+const OPERAND: &str = "a SNAFU number or `(`";
+const OPERATOR: &str = "an operator (`+`, `-` or `*`)";
+
+/// Arguments for calculating with day 25's SNAFU numbers.
+#[derive(Args)]
+pub struct SnafuArgs {
+    /// Expression like "1=-0-2 + 12111" with `+`, `-`, `*` and parentheses. `-` and `=` are
+    /// digits too, so `-` needs spaces around it to subtract. Read from stdin, one per line, when
+    /// omitted
+    expression: Option<String>,
+    /// Print the results in decimal instead of SNAFU
+    #[arg(long)]
+    to_decimal: bool,
+    /// Print this decimal number in SNAFU
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        conflicts_with_all = ["expression", "to_decimal"]
+    )]
+    from_decimal: Option<BigInt>,
+}
+
+/// The tokens of `text`: parentheses, `+` and `*` on their own, and everything else split at
+/// whitespace.
+fn tokenize(text: &str) -> Vec<&str> {
+    let single = |c: char| matches!(c, '(' | ')' | '+' | '*');
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = if single(c) {
+            c.len_utf8()
+        } else {
+            rest.find(|c: char| c.is_whitespace() || single(c))
+                .unwrap_or(rest.len())
+        };
+        tokens.push(&rest[..len]);
+        rest = rest[len..].trim_start();
+    }
+    tokens
+}
+
+/// A recursive descent parser working the expression out as it goes, with `*` binding tighter
+/// than `+` and `-`.
+struct Expression<'a> {
+    text: &'a str,
+    tokens: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> Expression<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.next).copied()
+    }
+
+    fn sum(&mut self) -> Result<Snafu, ParseError> {
+        let mut value = self.product()?;
+        while let Some(operator @ ("+" | "-")) = self.peek() {
+            self.next += 1;
+            let rhs = self.product()?;
+            value = if operator == "+" {
+                value + rhs
+            } else {
+                value - rhs
+            };
+        }
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<Snafu, ParseError> {
+        let mut value = self.operand()?;
+        while self.peek() == Some("*") {
+            self.next += 1;
+            value = value * self.operand()?;
+        }
+        Ok(value)
+    }
+
+    fn operand(&mut self) -> Result<Snafu, ParseError> {
+        let Some(token) = self.peek() else {
+            return Err(ParseError::missing(self.text, OPERAND));
+        };
+        self.next += 1;
+        match token {
+            "(" => {
+                let value = self.sum()?;
+                match self.peek() {
+                    Some(")") => {
+                        self.next += 1;
+                        Ok(value)
+                    }
+                    Some(token) => Err(ParseError::new(self.text, token, "an operator or `)`")),
+                    None => Err(ParseError::missing(self.text, "`)`")),
+                }
+            }
+            ")" | "+" | "*" => Err(ParseError::new(self.text, token, OPERAND)),
+            // a lone `-` here is the number -1
+            number => number
+                .parse()
+                .map_err(|e: ParseError| e.within(self.text, number)),
+        }
+    }
+}
+
+/// Works out an expression over SNAFU numbers.
+pub fn evaluate(text: &str) -> Result<Snafu, ParseError> {
+    let mut expression = Expression {
+        text,
+        tokens: tokenize(text),
+        next: 0,
+    };
+    let value = expression.sum()?;
+    match expression.peek() {
+        Some(token) => Err(ParseError::new(text, token, OPERATOR)),
+        None => Ok(value),
+    }
+}
+
+/// Prints what every expression comes to. Errors are returned ready to print, pointing at the
+/// character that's wrong.
+pub fn snafu(args: &SnafuArgs) -> Result<(), String> {
+    if let Some(n) = &args.from_decimal {
+        println!("{}", Snafu::from(n));
+        return Ok(());
+    }
+
+    let (input, source) = match &args.expression {
+        Some(expression) => (expression.clone(), "expression"),
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("stdin: {}", e))?;
+            (input, "stdin")
+        }
+    };
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let value =
+            evaluate(line).map_err(|e| e.within(&input, line).diagnostic(&input, source))?;
+        if args.to_decimal {
+            println!("{}", BigInt::from(&value));
+        } else {
+            println!("{}", value);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(text: &str) -> i128 {
+        i128::try_from(&evaluate(text).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(decimal("1=-0-2 + 12111"), 1747 + 906);
+        assert_eq!(decimal("1=-0-2 - 12111"), 1747 - 906);
+        assert_eq!(decimal("2=01"), 201);
+        assert_eq!(decimal("1+2*2"), 5);
+        assert_eq!(decimal("(1 + 2) * 2 - -"), 7);
+        assert_eq!(decimal("(1=11-2)"), 2022);
+        assert_eq!(evaluate("2 * 1=").unwrap().to_string(), "11");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |text| evaluate(text).unwrap_err().to_string();
+        assert_eq!(
+            error("1=-0-2 + 12311"),
+            "line 1, column 12: expected a SNAFU digit (`2`, `1`, `0`, `-` or `=`), found `3`"
+        );
+        assert_eq!(
+            error("1 2"),
+            "line 1, column 3: expected an operator (`+`, `-` or `*`), found `2`"
+        );
+        assert_eq!(
+            error("(1 + 2"),
+            "line 1, column 7: expected `)`, found the end of the line"
+        );
+        assert_eq!(
+            error("1 + * 2"),
+            "line 1, column 5: expected a SNAFU number or `(`, found `*`"
+        );
+        assert_eq!(
+            error(""),
+            "line 1, column 1: expected a SNAFU number or `(`, found the end of the line"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::{fmt::Display, panic};
+
+use common::{Answer, InputArgs, InputError};
+
+use crate::{answers::Answers, registry::Day};
+
+/// Widest an answer gets in the table before it is cut off.
+const ANSWER_WIDTH: usize = 20;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        };
+        f.pad(status)
+    }
+}
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+/// Shortens an answer to a single table cell. Multi-line answers like day 10's CRT picture only
+/// show their first line.
+fn cell(answer: &str) -> String {
+    let mut lines = answer.lines();
+    let first = lines.next().unwrap_or_default();
+    let truncated = first.chars().count() > ANSWER_WIDTH || lines.next().is_some();
+    if truncated {
+        let mut cell: String = first.chars().take(ANSWER_WIDTH - 1).collect();
+        cell.push('…');
+        cell
+    } else {
+        first.to_string()
+    }
+}
+
+fn row(day: u8, part: u8, profile: &str, expected: &str, actual: &str, status: Status) {
+    println!(
+        "{:>3}  {:>4}  {:<12}  {:<w$}  {:<w$}  {}",
+        day,
+        part,
+        profile,
+        cell(expected),
+        cell(actual),
+        status,
+        w = ANSWER_WIDTH
+    );
+}
+
+/// Runs every part of the given days against each profile and compares the result with the
+/// day's `answers.toml`. Rows are printed as they finish so slow days show progress. Returns
+/// whether nothing failed; answers or inputs that were never recorded are reported as missing
+/// but don't count as failures.
+pub fn verify(days: &[&Day], profiles: &[String]) -> bool {
+    println!(
+        "{:>3}  {:>4}  {:<12}  {:<w$}  {:<w$}  Status",
+        "Day",
+        "Part",
+        "Profile",
+        "Expected",
+        "Actual",
+        w = ANSWER_WIDTH
+    );
+
+    let mut summary = Summary::default();
+    let mut errors = Vec::new();
+    for day in days {
+        let answers = match Answers::load(day.day) {
+            Ok(answers) => answers,
+            Err(e) => {
+                errors.push(e.to_string());
+                summary.failed += 1;
+                continue;
+            }
+        };
+        for profile in profiles {
+            verify_profile(day, &answers, profile, &mut summary);
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        summary.passed, summary.failed, summary.missing
+    );
+    for error in errors {
+        eprintln!("{}", error);
+    }
+    summary.failed == 0
+}
+
+fn verify_profile(day: &Day, answers: &Answers, profile: &str, summary: &mut Summary) {
+    let input = match InputArgs::profile(profile).load(day.day) {
+        Ok(input) => input,
+        Err(InputError::Profile { .. }) => {
+            for part in 1..=day.parts {
+                let expected = answers.get(profile, part).map(Answer::from);
+                let expected = expected.map_or(String::new(), |e| e.to_string());
+                row(
+                    day.day,
+                    part,
+                    profile,
+                    &expected,
+                    "no input",
+                    Status::Missing,
+                );
+                summary.missing += 1;
+            }
+            return;
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            summary.failed += 1;
+            return;
+        }
+    };
+
+    for part in 1..=day.parts {
+        let expected = answers.get(profile, part).map(Answer::from);
+        let actual = panic::catch_unwind(|| day.solve(profile, part, &input));
+        let (actual, status) = match (&expected, actual) {
+            (_, Err(_)) => ("panicked".to_string(), Status::Fail),
+            (_, Ok(Err(e))) => {
+                let source = InputArgs::profile(profile).source(day.day);
+                eprintln!("{}", e.diagnostic(&input, &source));
+                ("parse error".to_string(), Status::Fail)
+            }
+            (None, Ok(Ok(actual))) => (actual.to_string(), Status::Missing),
+            (Some(expected), Ok(Ok(actual))) if *expected == actual => {
+                (actual.to_string(), Status::Pass)
+            }
+            (Some(_), Ok(Ok(actual))) => (actual.to_string(), Status::Fail),
+        };
+        match status {
+            Status::Pass => summary.passed += 1,
+            Status::Fail => summary.failed += 1,
+            Status::Missing => summary.missing += 1,
+        }
+        let expected = expected.map_or(String::new(), |e| e.to_string());
+        row(day.day, part, profile, &expected, &actual, status);
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+/// The answer to one part of a puzzle.
+///
+/// Most puzzles have a numeric answer, but some (like day 5's stack tops or
+/// day 10's CRT picture) are text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("-42", Answer::from(-42_isize).to_string());
+        assert_eq!("CMZ", Answer::from("CMZ").to_string());
+    }
+}
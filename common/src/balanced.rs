@@ -0,0 +1,453 @@
+//! Numbers written in a balanced base, where the digits run from `-(base / 2)` to `base / 2`
+//! instead of from 0, so negative numbers need no sign. Day 25's SNAFU numbers are balanced
+//! base 5, with `=` and `-` for the digits -2 and -1.
+//!
+//! Arithmetic works on the digits, carrying from one to the next like on paper, so numbers can
+//! have any number of digits.
+
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::Hash,
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use num_bigint::BigInt;
+
+use crate::ParseError;
+
+/// The symbols a balanced base writes its digits with. The base is the number of symbols, which
+/// has to be odd and at most 127.
+pub trait Alphabet: Clone + Copy + Debug + Default + PartialEq + Eq + Hash {
+    /// What the numbers are called in errors, like `SNAFU`.
+    const NAME: &'static str;
+
+    /// The symbol for every digit, from `-(base / 2)` up to `base / 2`.
+    const SYMBOLS: &'static [char];
+
+    fn base() -> i8 {
+        let base = Self::SYMBOLS.len();
+        assert!(
+            base % 2 == 1 && base <= i8::MAX as usize,
+            "a balanced base needs an odd number of digits below 128"
+        );
+        base as i8
+    }
+
+    fn symbol(digit: i8) -> char {
+        Self::SYMBOLS[(digit + Self::base() / 2) as usize]
+    }
+
+    fn digit(symbol: char) -> Option<i8> {
+        let position = Self::SYMBOLS.iter().position(|&s| s == symbol)?;
+        Some(position as i8 - Self::base() / 2)
+    }
+}
+
+/// A number in the balanced base of `A`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Balanced<A: Alphabet> {
+    /// The least significant first, without leading zeros, so zero has none.
+    digits: Vec<i8>,
+    alphabet: PhantomData<A>,
+}
+
+/// A number too large for the integer type it is converted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfRange;
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The number is out of range")
+    }
+}
+
+impl std::error::Error for OutOfRange {}
+
+impl<A: Alphabet> Balanced<A> {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Drops leading zeros from digits that are each already in the base.
+    fn from_digits(mut digits: Vec<i8>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Self {
+            digits,
+            alphabet: PhantomData,
+        }
+    }
+
+    /// Turns column sums, the least significant first, into digits by carrying whatever
+    /// doesn't fit in a digit over to the next column.
+    fn carry(columns: impl IntoIterator<Item = i64>) -> Self {
+        let base = A::base() as i64;
+        let mut columns = columns.into_iter();
+        let mut digits = Vec::new();
+        let mut carry = 0;
+        loop {
+            let value = match columns.next() {
+                Some(column) => column + carry,
+                None if carry != 0 => carry,
+                None => break,
+            };
+            let mut digit = value.rem_euclid(base);
+            if digit > base / 2 {
+                digit -= base;
+            }
+            carry = (value - digit) / base;
+            digits.push(digit as i8);
+        }
+        Self::from_digits(digits)
+    }
+
+    /// The digits, the most significant first. Zero has none.
+    pub fn digits(&self) -> impl Iterator<Item = i8> + '_ {
+        self.digits.iter().rev().copied()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Whether the number is below, at or above zero, which is what its leading digit says.
+    pub fn signum(&self) -> Ordering {
+        self.digits.last().map_or(Ordering::Equal, |d| d.cmp(&0))
+    }
+
+    fn add_digits(&self, rhs: &Self) -> Self {
+        let len = self.digits.len().max(rhs.digits.len());
+        let digit = |digits: &[i8], i| digits.get(i).copied().unwrap_or(0) as i64;
+        Self::carry((0..len).map(|i| digit(&self.digits, i) + digit(&rhs.digits, i)))
+    }
+
+    fn sub_digits(&self, rhs: &Self) -> Self {
+        self.add_digits(&-rhs)
+    }
+
+    /// Long multiplication, adding up the products of every pair of digits by column.
+    fn mul_digits(&self, rhs: &Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+        let mut columns = vec![0_i64; self.digits.len() + rhs.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            for (j, &b) in rhs.digits.iter().enumerate() {
+                columns[i + j] += a as i64 * b as i64;
+            }
+        }
+        Self::carry(columns)
+    }
+}
+
+impl<A: Alphabet> Neg for Balanced<A> {
+    type Output = Self;
+
+    /// Every digit's opposite is a digit too, so negating flips each one.
+    fn neg(mut self) -> Self {
+        for digit in &mut self.digits {
+            *digit = -*digit;
+        }
+        self
+    }
+}
+
+impl<A: Alphabet> Neg for &Balanced<A> {
+    type Output = Balanced<A>;
+
+    fn neg(self) -> Balanced<A> {
+        -self.clone()
+    }
+}
+
+macro_rules! arithmetic {
+    ($($trait:ident $method:ident $digits:ident),*) => {$(
+        impl<A: Alphabet> $trait for Balanced<A> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                self.$digits(&rhs)
+            }
+        }
+
+        impl<A: Alphabet> $trait for &Balanced<A> {
+            type Output = Balanced<A>;
+
+            fn $method(self, rhs: Self) -> Balanced<A> {
+                self.$digits(rhs)
+            }
+        }
+    )*};
+}
+
+arithmetic!(Add add add_digits, Sub sub sub_digits, Mul mul mul_digits);
+
+impl<A: Alphabet> Sum for Balanced<A> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |sum, n| sum + n)
+    }
+}
+
+impl<A: Alphabet> Product for Balanced<A> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::carry([1]), |product, n| product * n)
+    }
+}
+
+impl<A: Alphabet> Ord for Balanced<A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self - other).signum()
+    }
+}
+
+impl<A: Alphabet> PartialOrd for Balanced<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Accepts leading zeros, and points at the first symbol that isn't a digit.
+impl<A: Alphabet> FromStr for Balanced<A> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let symbols: Vec<String> = A::SYMBOLS
+            .iter()
+            .rev()
+            .map(|s| format!("`{}`", s))
+            .collect();
+        let (last, rest) = symbols.split_last().expect("alphabets have symbols");
+        let expected = format!("a {} digit ({} or {})", A::NAME, rest.join(", "), last);
+        if s.is_empty() {
+            return Err(ParseError::missing(s, expected));
+        }
+        let mut digits = s
+            .char_indices()
+            .map(|(i, c)| {
+                A::digit(c).ok_or_else(|| ParseError::new(s, &s[i..i + c.len_utf8()], &expected))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        digits.reverse();
+        Ok(Self::from_digits(digits))
+    }
+}
+
+impl<A: Alphabet> Display for Balanced<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "{}", A::symbol(0));
+        }
+        let s: String = self.digits().map(A::symbol).collect();
+        write!(f, "{}", s)
+    }
+}
+
+impl<A: Alphabet> From<i128> for Balanced<A> {
+    fn from(mut value: i128) -> Self {
+        let base = A::base() as i128;
+        let mut digits = Vec::new();
+        while value != 0 {
+            // rounding the quotient towards the nearest multiple of the base can't overflow
+            let mut quotient = value.div_euclid(base);
+            let mut digit = value.rem_euclid(base);
+            if digit > base / 2 {
+                digit -= base;
+                quotient += 1;
+            }
+            digits.push(digit as i8);
+            value = quotient;
+        }
+        Self::from_digits(digits)
+    }
+}
+
+impl<A: Alphabet> From<&BigInt> for Balanced<A> {
+    fn from(value: &BigInt) -> Self {
+        let base = BigInt::from(A::base());
+        let half = BigInt::from(A::base() / 2);
+        let mut value = value.clone();
+        let mut digits = Vec::new();
+        while value != BigInt::ZERO {
+            // shifting by half the base turns the balanced digit into an ordinary one
+            let shifted = &value + &half;
+            let mut remainder = &shifted % &base;
+            if remainder < BigInt::ZERO {
+                remainder += &base;
+            }
+            let digit = i8::try_from(remainder - &half).expect("digits fit in an i8");
+            value = (value - digit) / &base;
+            digits.push(digit);
+        }
+        Self::from_digits(digits)
+    }
+}
+
+impl<A: Alphabet> From<BigInt> for Balanced<A> {
+    fn from(value: BigInt) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl<A: Alphabet> From<&Balanced<A>> for BigInt {
+    fn from(value: &Balanced<A>) -> Self {
+        let base = BigInt::from(A::base());
+        value
+            .digits()
+            .fold(BigInt::ZERO, |n, digit| n * &base + digit)
+    }
+}
+
+impl<A: Alphabet> TryFrom<&Balanced<A>> for i128 {
+    type Error = OutOfRange;
+
+    fn try_from(value: &Balanced<A>) -> Result<Self, OutOfRange> {
+        // working the value out digit by digit can overflow on the way to `i128::MIN`
+        i128::try_from(BigInt::from(value)).map_err(|_| OutOfRange)
+    }
+}
+
+macro_rules! integers {
+    ($($int:ty),*) => {$(
+        impl<A: Alphabet> From<$int> for Balanced<A> {
+            fn from(value: $int) -> Self {
+                Self::from(value as i128)
+            }
+        }
+
+        impl<A: Alphabet> TryFrom<&Balanced<A>> for $int {
+            type Error = OutOfRange;
+
+            fn try_from(value: &Balanced<A>) -> Result<Self, OutOfRange> {
+                i128::try_from(value)?.try_into().map_err(|_| OutOfRange)
+            }
+        }
+    )*};
+}
+
+integers!(i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct Ternary;
+
+    impl Alphabet for Ternary {
+        const NAME: &'static str = "balanced ternary";
+        const SYMBOLS: &'static [char] = &['T', '0', '1'];
+    }
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct Snafu;
+
+    impl Alphabet for Snafu {
+        const NAME: &'static str = "SNAFU";
+        const SYMBOLS: &'static [char] = &['=', '-', '0', '1', '2'];
+    }
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct Septemvigesimal;
+
+    impl Alphabet for Septemvigesimal {
+        const NAME: &'static str = "balanced base 27";
+        const SYMBOLS: &'static [char] = &[
+            'z', 'y', 'x', 'w', 'v', 'u', 't', 's', 'r', 'q', 'p', 'o', 'n', '0', 'A', 'B', 'C',
+            'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+        ];
+    }
+
+    fn snafu(s: &str) -> Balanced<Snafu> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_snafu() {
+        assert_eq!(i128::try_from(&snafu("1=-0-2")), Ok(1747));
+        assert_eq!(Balanced::<Snafu>::from(2022).to_string(), "1=11-2");
+        assert_eq!(Balanced::<Snafu>::from(-2022).to_string(), "-2--1=");
+        assert_eq!(Balanced::<Snafu>::zero().to_string(), "0");
+        assert_eq!(snafu("0012"), snafu("12"));
+        assert_eq!(
+            i128::try_from(&(snafu("1=-0-2") + snafu("12111"))),
+            Ok(2653)
+        );
+        assert_eq!(
+            i128::try_from(&(snafu("1=-0-2") * snafu("12111"))),
+            Ok(1747 * 906)
+        );
+        assert_eq!(Balanced::<Ternary>::from(-5).to_string(), "T11");
+
+        let err = "1=3-".parse::<Balanced<Snafu>>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected a SNAFU digit (`2`, `1`, `0`, `-` or `=`), found `3`"
+        );
+        let err = "".parse::<Balanced<Ternary>>().unwrap_err();
+        assert_eq!(err.expected, "a balanced ternary digit (`1`, `0` or `T`)");
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let max = Balanced::<Snafu>::from(i128::MAX);
+        assert_eq!(i128::try_from(&max), Ok(i128::MAX));
+        assert_eq!(i128::try_from(&(&max + &snafu("1"))), Err(OutOfRange));
+        assert_eq!(
+            i128::try_from(&Balanced::<Snafu>::from(i128::MIN)),
+            Ok(i128::MIN)
+        );
+        assert_eq!(i8::try_from(&snafu("2===")), Err(OutOfRange));
+        assert_eq!(BigInt::from(&(&max * &max)), BigInt::from(i128::MAX).pow(2));
+    }
+
+    fn big() -> impl Strategy<Value = BigInt> {
+        (any::<bool>(), prop::collection::vec(any::<u32>(), 0..8)).prop_map(|(negative, words)| {
+            let n = BigInt::from(num_bigint::BigUint::new(words));
+            if negative {
+                -n
+            } else {
+                n
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn integers_round_trip(n in any::<i128>()) {
+            prop_assert_eq!(i128::try_from(&Balanced::<Snafu>::from(n)), Ok(n));
+            prop_assert_eq!(i128::try_from(&Balanced::<Ternary>::from(n)), Ok(n));
+            prop_assert_eq!(i128::try_from(&Balanced::<Septemvigesimal>::from(n)), Ok(n));
+        }
+
+        #[test]
+        fn big_integers_round_trip(n in big()) {
+            prop_assert_eq!(BigInt::from(&Balanced::<Snafu>::from(&n)), n.clone());
+            prop_assert_eq!(BigInt::from(&Balanced::<Septemvigesimal>::from(&n)), n);
+        }
+
+        #[test]
+        fn text_round_trips(n in big()) {
+            let snafu = Balanced::<Snafu>::from(&n);
+            prop_assert_eq!(snafu.to_string().parse::<Balanced<Snafu>>().unwrap(), snafu);
+            let ternary = Balanced::<Ternary>::from(&n);
+            prop_assert_eq!(ternary.to_string().parse::<Balanced<Ternary>>().unwrap(), ternary);
+        }
+
+        #[test]
+        fn arithmetic_matches_big_integers(a in big(), b in big()) {
+            let (x, y) = (Balanced::<Snafu>::from(&a), Balanced::<Snafu>::from(&b));
+            prop_assert_eq!(BigInt::from(&(&x + &y)), &a + &b);
+            prop_assert_eq!(BigInt::from(&(&x - &y)), &a - &b);
+            prop_assert_eq!(BigInt::from(&(&x * &y)), &a * &b);
+            prop_assert_eq!(BigInt::from(&-&x), -&a);
+            prop_assert_eq!(x.cmp(&y), a.cmp(&b));
+        }
+    }
+}
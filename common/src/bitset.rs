@@ -0,0 +1,153 @@
+use std::fmt::Debug;
+
+/// A set of small numbers (below 64) packed into the bits of a `u64`, which
+/// makes it cheap to copy and hash as part of a cache key. Day 16 uses it for
+/// the set of open valves.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet(u64);
+
+impl BitSet {
+    /// Largest number of elements a set can hold.
+    pub const CAPACITY: usize = 64;
+
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// The set of every number below `n`.
+    pub fn full(n: usize) -> Self {
+        assert!(n <= Self::CAPACITY, "a bit set holds at most 64 elements");
+        Self(
+            u64::MAX
+                .checked_shr(Self::CAPACITY as u32 - n as u32)
+                .unwrap_or(0),
+        )
+    }
+
+    /// The set whose elements are the one bits of `bits`.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    fn bit(element: usize) -> u64 {
+        assert!(
+            element < Self::CAPACITY,
+            "{} doesn't fit in a bit set",
+            element
+        );
+        1 << element
+    }
+
+    pub fn contains(self, element: usize) -> bool {
+        element < Self::CAPACITY && self.0 & (1 << element) != 0
+    }
+
+    /// Adds `element`, returning whether it was new.
+    pub fn insert(&mut self, element: usize) -> bool {
+        let new = !self.contains(element);
+        self.0 |= Self::bit(element);
+        new
+    }
+
+    /// Removes `element`, returning whether it was there.
+    pub fn remove(&mut self, element: usize) -> bool {
+        let present = self.contains(element);
+        self.0 &= !Self::bit(element);
+        present
+    }
+
+    /// A copy of the set with `element` added.
+    pub fn with(mut self, element: usize) -> Self {
+        self.insert(element);
+        self
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn is_disjoint(self, other: Self) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    pub fn is_subset(self, other: Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// The elements in increasing order.
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let element = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(element)
+        })
+    }
+}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for element in iter {
+            set.insert(element);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_set() {
+        let mut set: BitSet = [3, 0, 63].into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(63));
+        assert!(!set.contains(64));
+        assert!(!set.insert(3));
+        assert!(set.insert(5));
+        assert!(set.remove(0));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 5, 63]);
+        assert_eq!(format!("{:?}", set), "{3, 5, 63}");
+
+        let other = BitSet::new().with(5).with(7);
+        assert_eq!(set.union(other).len(), 4);
+        assert_eq!(set.intersection(other), BitSet::new().with(5));
+        assert!(set.difference(other).is_disjoint(other));
+        assert!(BitSet::new().with(7).is_subset(other));
+
+        assert_eq!(BitSet::full(0), BitSet::new());
+        assert_eq!(BitSet::full(3).bits(), 0b111);
+        assert_eq!(BitSet::full(64).len(), 64);
+    }
+}
@@ -0,0 +1,327 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+/// How a [`CycleDetector`] looks for a repeated state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Remembers the fingerprint of every step. Finds the cycle as soon as
+    /// the first state repeats, in a single pass.
+    #[default]
+    Hashing,
+    /// Brent's algorithm, which only keeps two fingerprints around but has to
+    /// run the simulation again from the start to find where the cycle begins.
+    Brent,
+}
+
+/// The simulation repeats itself every `length` steps from step `start` on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CycleError {
+    /// No state repeated within the step limit.
+    NotFound { steps: usize },
+    /// A fingerprint repeated but the simulation didn't, because the
+    /// fingerprint or the metric differed one or more cycles later.
+    Unconfirmed { cycle: Cycle, step: usize },
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CycleError::NotFound { steps } => {
+                write!(f, "No cycle found within {} steps", steps)
+            }
+            CycleError::Unconfirmed { cycle, step } => write!(
+                f,
+                "The cycle of length {} starting at step {} stopped repeating at step {}",
+                cycle.length, cycle.start, step
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// What a search for a cycle ended with.
+enum Search {
+    /// The step asked for came before any cycle.
+    Reached(i64),
+    /// A cycle, with the metric of every step up to at least the end of its
+    /// first repetition.
+    Found(Cycle, Vec<i64>),
+}
+
+/// Finds where a long-running simulation starts repeating itself and uses
+/// that to work out a metric (like a tower's height) after more steps than
+/// could be simulated.
+///
+/// `step` advances the state by one step. `fingerprint` identifies a state; it
+/// may be lossy (day 17 only looks at the top of the tower) as long as two
+/// states with the same fingerprint behave the same from then on. `metric`
+/// must grow by the same amount in every repetition of the cycle. Since a
+/// lossy fingerprint can still repeat by accident, a cycle is only trusted
+/// after the simulation has been run through it once more (or as often as set
+/// with [`CycleDetector::confirmations`]).
+pub struct CycleDetector<S, Step, Fingerprint, Metric> {
+    initial: S,
+    step: Step,
+    fingerprint: Fingerprint,
+    metric: Metric,
+    strategy: Strategy,
+    confirmations: usize,
+    limit: usize,
+}
+
+impl<S, K, Step, Fingerprint, Metric> CycleDetector<S, Step, Fingerprint, Metric>
+where
+    S: Clone,
+    K: Clone + Eq + Hash,
+    Step: FnMut(&mut S),
+    Fingerprint: FnMut(&S) -> K,
+    Metric: FnMut(&S) -> i64,
+{
+    pub fn new(initial: S, step: Step, fingerprint: Fingerprint, metric: Metric) -> Self {
+        Self {
+            initial,
+            step,
+            fingerprint,
+            metric,
+            strategy: Strategy::default(),
+            confirmations: 1,
+            limit: 10_000_000,
+        }
+    }
+
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Number of extra times the simulation is run through a cycle to confirm
+    /// it, at least one.
+    pub fn confirmations(mut self, confirmations: usize) -> Self {
+        assert!(confirmations > 0, "a cycle needs to be seen repeating once");
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Most steps to simulate while looking for a cycle.
+    pub fn limit(mut self, steps: usize) -> Self {
+        self.limit = steps;
+        self
+    }
+
+    /// Finds the first cycle of the simulation.
+    pub fn find_cycle(&mut self) -> Result<Cycle, CycleError> {
+        match self.search(usize::MAX)? {
+            Search::Found(cycle, _) => Ok(cycle),
+            Search::Reached(_) => unreachable!("the search gives up before usize::MAX steps"),
+        }
+    }
+
+    /// The metric after `n` steps, simulated directly if the simulation doesn't
+    /// repeat before then and extrapolated from the cycle otherwise.
+    pub fn metric_at(&mut self, n: usize) -> Result<i64, CycleError> {
+        let (cycle, metrics) = match self.search(n)? {
+            Search::Reached(metric) => return Ok(metric),
+            Search::Found(cycle, metrics) => (cycle, metrics),
+        };
+        if let Some(&metric) = metrics.get(n) {
+            return Ok(metric);
+        }
+        let Cycle { start, length } = cycle;
+        let per_cycle = metrics[start + length] - metrics[start];
+        let cycles = (n - start) / length;
+        let remainder = (n - start) % length;
+        Ok(metrics[start + remainder] + cycles as i64 * per_cycle)
+    }
+
+    fn search(&mut self, n: usize) -> Result<Search, CycleError> {
+        match self.strategy {
+            Strategy::Hashing => self.search_hashing(n),
+            Strategy::Brent => self.search_brent(n),
+        }
+    }
+
+    fn search_hashing(&mut self, n: usize) -> Result<Search, CycleError> {
+        let mut state = self.initial.clone();
+        let mut seen = HashMap::new();
+        let mut fingerprints = Vec::new();
+        let mut metrics = Vec::new();
+        let cycle = loop {
+            let i = metrics.len();
+            metrics.push((self.metric)(&state));
+            if i == n {
+                return Ok(Search::Reached(metrics[i]));
+            }
+            let fingerprint = (self.fingerprint)(&state);
+            if let Some(&start) = seen.get(&fingerprint) {
+                fingerprints.push(fingerprint);
+                break Cycle {
+                    start,
+                    length: i - start,
+                };
+            }
+            if i >= self.limit {
+                return Err(CycleError::NotFound { steps: i });
+            }
+            seen.insert(fingerprint.clone(), i);
+            fingerprints.push(fingerprint);
+            (self.step)(&mut state);
+        };
+
+        // run on through the next cycles, comparing every step with the one a cycle earlier
+        let per_cycle = metrics[cycle.start + cycle.length] - metrics[cycle.start];
+        for _ in 0..self.confirmations * cycle.length {
+            (self.step)(&mut state);
+            let i = metrics.len();
+            let fingerprint = (self.fingerprint)(&state);
+            metrics.push((self.metric)(&state));
+            if fingerprint != fingerprints[i - cycle.length]
+                || metrics[i] - metrics[i - cycle.length] != per_cycle
+            {
+                return Err(CycleError::Unconfirmed { cycle, step: i });
+            }
+            fingerprints.push(fingerprint);
+        }
+        Ok(Search::Found(cycle, metrics))
+    }
+
+    fn search_brent(&mut self, n: usize) -> Result<Search, CycleError> {
+        // find the length of the cycle: the tortoise waits at powers of two for the hare to
+        // come around to it
+        let mut hare = self.initial.clone();
+        let mut steps = 0;
+        let mut tortoise = None;
+        let mut power = 1;
+        let mut length = 0;
+        loop {
+            if steps == n {
+                return Ok(Search::Reached((self.metric)(&hare)));
+            }
+            let fingerprint = (self.fingerprint)(&hare);
+            if tortoise.as_ref() == Some(&fingerprint) {
+                break;
+            }
+            if steps >= self.limit {
+                return Err(CycleError::NotFound { steps });
+            }
+            if tortoise.is_none() || power == length {
+                tortoise = Some(fingerprint);
+                power *= 2;
+                length = 0;
+            }
+            (self.step)(&mut hare);
+            steps += 1;
+            length += 1;
+        }
+
+        // find the start: a hare one cycle ahead meets the tortoise at the start of the cycle
+        let mut tortoise = self.initial.clone();
+        let mut hare = self.initial.clone();
+        for _ in 0..length {
+            (self.step)(&mut hare);
+        }
+        let mut metrics = vec![(self.metric)(&tortoise)];
+        while (self.fingerprint)(&tortoise) != (self.fingerprint)(&hare) {
+            (self.step)(&mut tortoise);
+            (self.step)(&mut hare);
+            metrics.push((self.metric)(&tortoise));
+        }
+        let start = metrics.len() - 1;
+        let cycle = Cycle { start, length };
+
+        // walk both through the next cycles, which also records the metrics of the first one
+        let mut per_cycle = None;
+        for _ in 0..self.confirmations * length {
+            let lead = (self.metric)(&hare) - metrics[metrics.len() - 1];
+            if *per_cycle.get_or_insert(lead) != lead {
+                return Err(CycleError::Unconfirmed {
+                    cycle,
+                    step: metrics.len() - 1 + length,
+                });
+            }
+            (self.step)(&mut tortoise);
+            (self.step)(&mut hare);
+            metrics.push((self.metric)(&tortoise));
+            if (self.fingerprint)(&tortoise) != (self.fingerprint)(&hare) {
+                return Err(CycleError::Unconfirmed {
+                    cycle,
+                    step: metrics.len() - 1 + length,
+                });
+            }
+        }
+        Ok(Search::Found(cycle, metrics))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pseudo-random walk through the numbers below 1000 that ends up in a
+    /// cycle, adding up the numbers it visits in the second field.
+    fn walk(state: &mut (u64, i64)) {
+        state.0 = (state.0 * state.0 + 7) % 1000;
+        state.1 += state.0 as i64;
+    }
+
+    fn brute_force(n: usize) -> i64 {
+        let mut state = (3, 0);
+        for _ in 0..n {
+            walk(&mut state);
+        }
+        state.1
+    }
+
+    #[test]
+    fn test_strategies_agree() {
+        for strategy in [Strategy::Hashing, Strategy::Brent] {
+            let mut detector = CycleDetector::new((3, 0), walk, |s| s.0, |s| s.1)
+                .strategy(strategy)
+                .confirmations(2);
+            let cycle = detector.find_cycle().unwrap();
+            // 3, 16, 263, 176, 983, 296, 623, 136, 503, 16, ...
+            assert_eq!(
+                cycle,
+                Cycle {
+                    start: 1,
+                    length: 8
+                },
+                "{:?}",
+                strategy
+            );
+            for n in [0, 1, 5, 6, 7, 100, 1001] {
+                assert_eq!(detector.metric_at(n), Ok(brute_force(n)), "{:?}", strategy);
+            }
+            assert_eq!(
+                detector.metric_at(1_000_000_000_000),
+                Ok(brute_force(8) + 124_999_999_999 * (brute_force(9) - brute_force(1))),
+            );
+        }
+    }
+
+    #[test]
+    fn test_unconfirmed() {
+        // the parity of the number repeats long before the number itself does
+        for strategy in [Strategy::Hashing, Strategy::Brent] {
+            let mut detector =
+                CycleDetector::new((3, 0), walk, |s| s.0 % 2, |s| s.1).strategy(strategy);
+            assert!(
+                matches!(detector.find_cycle(), Err(CycleError::Unconfirmed { .. })),
+                "{:?}",
+                strategy
+            );
+        }
+
+        let mut detector = CycleDetector::new(0u64, |s| *s += 1, |&s| s, |&s| s as i64).limit(50);
+        assert_eq!(detector.metric_at(20), Ok(20));
+        assert_eq!(
+            detector.metric_at(100),
+            Err(CycleError::NotFound { steps: 50 })
+        );
+    }
+}
@@ -0,0 +1,386 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{ParseError, Point2};
+
+/// Column and row of a cell in a [`Grid`], counted from the top left.
+pub type Position = Point2<usize>;
+
+/// Column and row of a cell in a [`SparseGrid`], which may be negative.
+pub type SparsePosition = Point2<i64>;
+
+/// Turns every line of `input` into its cells, reporting characters `cell` doesn't know as parse
+/// errors.
+fn parse_cells<'a, T: 'a>(
+    input: &'a str,
+    expected: &'a str,
+    cell: impl Fn(char) -> Option<T> + 'a,
+) -> impl Iterator<Item = (&'a str, Result<Vec<T>, ParseError>)> + 'a {
+    input.lines().map(move |line| {
+        let cells = line
+            .char_indices()
+            .map(|(i, c)| {
+                cell(c).ok_or_else(|| {
+                    let token = &line[i..i + c.len_utf8()];
+                    ParseError::new(line, token, expected).within(input, line)
+                })
+            })
+            .collect();
+        (line, cells)
+    })
+}
+
+/// A rectangular grid stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a character map, one row per line. `cell` turns each character into a cell and
+    /// returns `None` for characters that don't belong on the map, which are reported as
+    /// `expected` something else. All rows must be as long as the first.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (line, row) in parse_cells(input, expected, cell) {
+            let row = row?;
+            let width = *width.get_or_insert(row.len());
+            if row.len() > width {
+                let (i, _) = line.char_indices().nth(width).unwrap_or_default();
+                let expected = format!("the end of a row of {} cells", width);
+                return Err(ParseError::new(line, &line[i..], expected).within(input, line));
+            } else if row.len() < width {
+                let expected = format!("a row of {} cells", width);
+                return Err(ParseError::missing(line, expected).within(input, line));
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.y * self.width + position.x])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.y * self.width + position.x])
+    }
+
+    /// Moves `position` by `offset` (a direction or a [`Point2<i64>`]), or returns `None` if
+    /// that leaves the grid.
+    pub fn step(&self, position: Position, offset: impl Into<Point2<i64>>) -> Option<Position> {
+        position.step(offset).filter(|&next| self.contains(next))
+    }
+
+    /// The 4-connected neighbours of `position` that are on the grid.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        position.neighbors4().filter(|&next| self.contains(next))
+    }
+
+    /// The 8-connected neighbours of `position` that are on the grid.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        position.neighbors8().filter(|&next| self.contains(next))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        assert!(x < self.width, "column {} is off the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is off the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is off the grid", position))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A grid that only stores occupied cells, for maps without fixed bounds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<SparsePosition, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a character map, one row per line, with the first character of the first line at
+    /// `(0, 0)`. `cell` returns `Some(None)` for characters that leave the cell empty and `None`
+    /// for characters that don't belong on the map. Rows may have different lengths.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<Option<T>>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = HashMap::new();
+        for (y, (_, row)) in parse_cells(input, expected, cell).enumerate() {
+            for (x, cell) in row?.into_iter().enumerate() {
+                if let Some(cell) = cell {
+                    cells.insert(Point2::new(x as i64, y as i64), cell);
+                }
+            }
+        }
+        Ok(Self { cells })
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: SparsePosition) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    pub fn get(&self, position: SparsePosition) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn get_mut(&mut self, position: SparsePosition) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+
+    pub fn insert(&mut self, position: SparsePosition, cell: T) -> Option<T> {
+        self.cells.insert(position, cell)
+    }
+
+    pub fn remove(&mut self, position: SparsePosition) -> Option<T> {
+        self.cells.remove(&position)
+    }
+
+    /// Occupied positions, in no particular order.
+    pub fn positions(&self) -> impl Iterator<Item = SparsePosition> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Occupied cells with their positions, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (SparsePosition, &T)> + '_ {
+        self.cells.iter().map(|(&position, cell)| (position, cell))
+    }
+
+    /// Occupied cells in row `y` with their columns, in no particular order.
+    pub fn row(&self, y: i64) -> impl Iterator<Item = (i64, &T)> + '_ {
+        self.iter()
+            .filter(move |(position, _)| position.y == y)
+            .map(|(position, cell)| (position.x, cell))
+    }
+
+    /// Occupied cells in column `x` with their rows, in no particular order.
+    pub fn column(&self, x: i64) -> impl Iterator<Item = (i64, &T)> + '_ {
+        self.iter()
+            .filter(move |(position, _)| position.x == x)
+            .map(|(position, cell)| (position.y, cell))
+    }
+
+    /// Top left and bottom right corner of the smallest rectangle around every occupied cell.
+    pub fn bounds(&self) -> Option<(SparsePosition, SparsePosition)> {
+        let mut positions = self.positions();
+        let first = positions.next()?;
+        Some(positions.fold((first, first), |(min, max), p| {
+            (
+                Point2::new(min.x.min(p.x), min.y.min(p.y)),
+                Point2::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+}
+
+impl<T> FromIterator<(SparsePosition, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (SparsePosition, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Renders the bounding rectangle of the occupied cells, with `.` for empty ones.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            if y > min.y {
+                writeln!(f)?;
+            }
+            for x in min.x..=max.x {
+                match self.get(Point2::new(x, y)) {
+                    Some(cell) => write!(f, "{}", cell)?,
+                    None => write!(f, ".")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn test_dense_grid() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point2::new(2, 1)], 6);
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(1).rev().collect::<Vec<_>>(), [&5, &2]);
+        assert_eq!(
+            grid.neighbors4(Point2::new(0, 1)).collect::<Vec<_>>(),
+            [Point2::new(0, 0), Point2::new(1, 1)]
+        );
+        assert_eq!(grid.neighbors8(Point2::new(1, 0)).count(), 5);
+        assert_eq!(grid.position(|&d| d > 4), Some(Point2::new(1, 1)));
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn test_dense_grid_errors() {
+        let err = digits("123\n4x6\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 2, "x"));
+        let err = digits("123\n4567\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 4, "7"));
+        let err = digits("123\n45\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "a row of 3 cells");
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::parse("..#\n#.\n", "`#` or `.`", |c| match c {
+            '#' => Some(Some('#')),
+            '.' => Some(None),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(Point2::new(2, 0)) && grid.contains(Point2::new(0, 1)));
+        grid.insert(Point2::new(-1, 2), '#');
+        assert_eq!(grid.bounds(), Some((Point2::new(-1, 0), Point2::new(2, 2))));
+        assert_eq!(grid.to_string(), "...#\n.#..\n#...");
+        assert_eq!(grid.row(1).collect::<Vec<_>>(), [(0, &'#')]);
+        assert_eq!(
+            Point2::new(0, 0)
+                .neighbors8()
+                .filter(|&p| grid.contains(p))
+                .count(),
+            1
+        );
+    }
+}
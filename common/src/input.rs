@@ -0,0 +1,195 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+
+pub const SAMPLE_PROFILE: &str = "sample";
+pub const REAL_PROFILE: &str = "real";
+
+/// Command line arguments selecting which puzzle input to solve.
+#[derive(Args, Clone, Debug)]
+pub struct InputArgs {
+    /// Named input from the day's data directory: `real` reads input.txt, `sample` reads
+    /// sample.txt and any other name (like `alt-account`) reads <name>.txt
+    #[arg(long, default_value = REAL_PROFILE)]
+    pub profile: String,
+
+    /// Read the input from this file instead of the profile's file, or from stdin if it is `-`.
+    /// The profile still decides which puzzle constants are used.
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+}
+
+impl Default for InputArgs {
+    fn default() -> Self {
+        Self {
+            profile: REAL_PROFILE.to_string(),
+            input: None,
+        }
+    }
+}
+
+impl InputArgs {
+    pub fn profile(profile: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+            input: None,
+        }
+    }
+
+    /// Where the selected input is read from, for error messages.
+    pub fn source(&self, day: u8) -> String {
+        match &self.input {
+            Some(path) if path.as_os_str() == "-" => "<stdin>".to_string(),
+            Some(path) => path.display().to_string(),
+            None => match profile_path(day, &self.profile) {
+                Ok(path) => path.display().to_string(),
+                Err(_) => self.profile.clone(),
+            },
+        }
+    }
+
+    /// Reads the selected input for the given day.
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        match &self.input {
+            Some(path) if path.as_os_str() == "-" => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+            Some(path) => fs::read_to_string(path).map_err(|source| InputError::File {
+                path: path.clone(),
+                source,
+            }),
+            None => {
+                let path = profile_path(day, &self.profile)?;
+                fs::read_to_string(&path).map_err(|source| InputError::Profile {
+                    day,
+                    profile: self.profile.clone(),
+                    path,
+                    source,
+                })
+            }
+        }
+    }
+}
+
+/// Crate directory of the given day.
+pub fn day_dir(day: u8) -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir
+        .parent()
+        .unwrap_or(manifest_dir)
+        .join("solutions")
+        .join(format!("day{:02}", day))
+}
+
+/// Directory holding the inputs for the given day.
+pub fn data_dir(day: u8) -> PathBuf {
+    day_dir(day).join("data")
+}
+
+/// File a named input profile is stored in. Names are limited to letters, digits, `_` and `-`
+/// so a profile can't point outside the day's data directory.
+pub fn profile_path(day: u8, profile: &str) -> Result<PathBuf, InputError> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+    if profile.is_empty() || !profile.chars().all(valid) {
+        return Err(InputError::ProfileName(profile.to_string()));
+    }
+    let file_name = match profile {
+        REAL_PROFILE => "input".to_string(),
+        name => name.to_string(),
+    };
+    Ok(data_dir(day).join(file_name + ".txt"))
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Profile {
+        day: u8,
+        profile: String,
+        path: PathBuf,
+        source: io::Error,
+    },
+    File {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Stdin(io::Error),
+    /// A profile name with characters other than letters, digits, `_` and `-`.
+    ProfileName(String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Profile {
+                day,
+                profile,
+                path,
+                source,
+            } if source.kind() == io::ErrorKind::NotFound => write!(
+                f,
+                "No `{}` input for day {}: {} does not exist",
+                profile,
+                day,
+                path.display()
+            ),
+            InputError::Profile { path, source, .. } | InputError::File { path, source } => {
+                write!(f, "Could not read {}: {}", path.display(), source)
+            }
+            InputError::Stdin(source) => write!(f, "Could not read input from stdin: {}", source),
+            InputError::ProfileName(profile) => write!(
+                f,
+                "Invalid profile `{}`: names may only use letters, digits, `_` and `-`",
+                profile
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Profile { source, .. }
+            | InputError::File { source, .. }
+            | InputError::Stdin(source) => Some(source),
+            InputError::ProfileName(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_path() {
+        assert!(profile_path(15, "real")
+            .unwrap()
+            .ends_with("solutions/day15/data/input.txt"));
+        assert!(profile_path(3, "alt-account")
+            .unwrap()
+            .ends_with("solutions/day03/data/alt-account.txt"));
+        for profile in ["../../../etc/passwd", "/tmp/input", "a.b", ""] {
+            assert!(matches!(
+                profile_path(1, profile),
+                Err(InputError::ProfileName(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_missing_profile() {
+        let err = InputArgs::profile("does-not-exist").load(1).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("No `does-not-exist` input for day 1: "));
+    }
+}
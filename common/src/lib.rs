@@ -0,0 +1,22 @@
+mod answer;
+pub mod balanced;
+mod bitset;
+pub mod cycle;
+pub mod grid;
+pub mod input;
+pub mod memo;
+mod parse;
+pub mod point;
+pub mod search;
+mod solution;
+
+pub use answer::Answer;
+pub use balanced::Balanced;
+pub use bitset::BitSet;
+pub use cycle::{Cycle, CycleDetector};
+pub use grid::{Grid, SparseGrid};
+pub use input::{InputArgs, InputError};
+pub use memo::{Memo, MemoStats};
+pub use parse::{parse_lines, parse_number, ParseError};
+pub use point::{Direction4, Direction6, Direction8, Point2, Point3};
+pub use solution::{init_tracing, main, print_answer, run, Explanation, Solution};
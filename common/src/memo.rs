@@ -0,0 +1,184 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    env,
+    fmt::Display,
+    hash::Hash,
+};
+
+/// Environment variable that makes every [`Memo`] print its [`MemoStats`] to
+/// stderr when it is dropped.
+pub const STATS_VAR: &str = "AOC_CACHE_STATS";
+
+/// How well a [`Memo`] did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries thrown away to stay within the size bound.
+    pub evictions: u64,
+}
+
+impl MemoStats {
+    /// Share of lookups that found a value, between 0 and 1.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} evictions",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.evictions
+        )
+    }
+}
+
+/// A cache of results for dynamic programming and memoized recursion that
+/// keeps track of how often it is useful.
+///
+/// A bounded memo forgets its oldest entries once it is full, which keeps
+/// searches with huge state spaces from running out of memory at the price of
+/// recomputing some results.
+pub struct Memo<K, V> {
+    name: &'static str,
+    values: HashMap<K, V>,
+    /// Keys in the order they were inserted, only kept for bounded memos.
+    order: VecDeque<K>,
+    capacity: Option<usize>,
+    stats: MemoStats,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    /// An unbounded memo. `name` identifies it when its statistics are printed.
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            values: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: None,
+            stats: MemoStats::default(),
+        }
+    }
+
+    /// A memo that holds at most `capacity` entries.
+    pub fn bounded(name: &'static str, capacity: usize) -> Self {
+        assert!(capacity > 0, "a memo needs room for at least one entry");
+        let mut memo = Self::new(name);
+        memo.capacity = Some(capacity);
+        memo
+    }
+
+    /// Looks up the value for `key`, counting the lookup as a hit or a miss.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.values.get(key).cloned();
+        if value.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+        value
+    }
+
+    /// Remembers `value` for `key`, evicting the oldest entry if the memo is
+    /// full.
+    pub fn insert(&mut self, key: K, value: V) {
+        let Some(capacity) = self.capacity else {
+            self.values.insert(key, value);
+            return;
+        };
+        if self.values.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+            if self.order.len() > capacity {
+                let oldest = self.order.pop_front().expect("the memo is over capacity");
+                self.values.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+        }
+    }
+
+    /// Returns the value for `key`, computing and remembering it first if it
+    /// isn't known yet. `compute` gets the memo back so recursive solvers can
+    /// keep using it.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = compute(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        if env::var_os(STATS_VAR).is_some() {
+            eprintln!(
+                "{} cache: {} entries, {}",
+                self.name,
+                self.values.len(),
+                self.stats
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn test_memoized_recursion() {
+        let mut memo = Memo::new("fibonacci");
+        assert_eq!(fibonacci(90, &mut memo), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 89);
+        let stats = memo.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (87, 89, 0));
+        assert_eq!(
+            stats.to_string(),
+            "87 hits, 89 misses (49.4% hit rate), 0 evictions"
+        );
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut memo = Memo::bounded("squares", 2);
+        for n in 0..4 {
+            memo.insert(n, n * n);
+        }
+        memo.insert(3, 9);
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get(&1), None);
+        assert_eq!(memo.get(&2), Some(4));
+        assert_eq!(memo.get(&3), Some(9));
+        assert_eq!(memo.stats().evictions, 2);
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt::Display;
+
+/// A malformed piece of puzzle input.
+///
+/// Parsers usually only see part of the input (a line, or a monkey's section
+/// on day 11), so the error is created relative to the text the parser was
+/// given and then moved into the surrounding text with [`ParseError::within`]
+/// as it is passed up. Positions are found from where the offending token's
+/// slice points, which is why tokens must be slices of the text rather than
+/// copies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Day the input belongs to, filled in by [`ParseError::in_day`].
+    pub day: Option<u8>,
+    /// 1-based line of the offending token.
+    pub line: usize,
+    /// 1-based column (in characters) of the offending token.
+    pub column: usize,
+    /// The offending token, empty if the input ended early.
+    pub token: String,
+    /// What the parser expected to find instead.
+    pub expected: String,
+}
+
+/// Byte offset of `slice` in `text`, clamped to the end of `text` if `slice`
+/// isn't part of it.
+fn offset(text: &str, slice: &str) -> usize {
+    (slice.as_ptr() as usize)
+        .checked_sub(text.as_ptr() as usize)
+        .filter(|&offset| offset <= text.len())
+        .unwrap_or(text.len())
+}
+
+/// 1-based line and column of a byte offset into `text`.
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+impl ParseError {
+    /// An unexpected `token`, which must be a slice of `text`.
+    pub fn new(text: &str, token: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(text, offset(text, token));
+        Self {
+            day: None,
+            line,
+            column,
+            token: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// `text` ended before the parser found what it expected.
+    pub fn missing(text: &str, expected: impl Into<String>) -> Self {
+        Self::new(text, &text[text.len()..], expected)
+    }
+
+    /// Moves an error created relative to `text` into `outer`, which `text` is a
+    /// slice of.
+    pub fn within(mut self, outer: &str, text: &str) -> Self {
+        let (line, column) = position(outer, offset(outer, text));
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+
+    /// Records which day's input the error is in.
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Renders the error with the offending line of `input` underlined, in the
+    /// style of a compiler diagnostic. `source` names where the input came from.
+    pub fn diagnostic(&self, input: &str, source: &str) -> String {
+        let text = input.lines().nth(self.line - 1).unwrap_or_default();
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = "^".repeat(self.token.chars().count().max(1));
+        format!(
+            "error: {}\n{gutter}--> {}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}{}",
+            self,
+            source,
+            self.line,
+            self.column,
+            self.line,
+            text,
+            " ".repeat(self.column - 1),
+            underline,
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        if self.token.is_empty() {
+            write!(f, "found the end of the line")
+        } else {
+            write!(f, "found `{}`", self.token)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses every line of `input` with `parse`, moving errors to the right line.
+pub fn parse_lines<T>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Parses `token` as a number, reporting it as the offending token of `text` if
+/// it isn't one.
+pub fn parse_number<T: std::str::FromStr>(text: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(text, token, "a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let input = "Sensor at x=2, y=18\nSensor at x=9, y=abc\n";
+        let line = input.lines().nth(1).unwrap();
+        let token = &line[17..];
+        let err = parse_number::<isize>(line, token)
+            .unwrap_err()
+            .within(input, line)
+            .in_day(15);
+        assert_eq!((err.line, err.column), (2, 18));
+        assert_eq!(
+            err.to_string(),
+            "day 15, line 2, column 18: expected a number, found `abc`"
+        );
+        assert_eq!(
+            err.diagnostic(input, "input.txt"),
+            "error: day 15, line 2, column 18: expected a number, found `abc`\n \
+             --> input.txt:2:18\n  |\n2 | Sensor at x=9, y=abc\n  |                  ^^^"
+        );
+    }
+
+    #[test]
+    fn test_missing() {
+        let input = "1=-0-2\n12111\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = ParseError::missing(line, "a digit").within(input, line);
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: expected a digit, found the end of the line"
+        );
+    }
+}
@@ -0,0 +1,457 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{parse_number, ParseError};
+
+/// A number that can be used as a coordinate.
+pub trait Scalar:
+    Copy
+    + Ord
+    + Hash
+    + Debug
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Distance between `self` and `other`, which is never negative.
+    fn abs_diff(self, other: Self) -> Self;
+
+    /// `self + offset`, or `None` if that doesn't fit in the type.
+    fn checked_offset(self, offset: i64) -> Option<Self>;
+}
+
+/// A coordinate type that can go negative, so points can be rotated and negated.
+pub trait Signed: Scalar + Neg<Output = Self> {}
+
+macro_rules! scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs_diff(self, other: Self) -> Self {
+                if self > other {
+                    self - other
+                } else {
+                    other - self
+                }
+            }
+
+            fn checked_offset(self, offset: i64) -> Option<Self> {
+                (self as i128)
+                    .checked_add(offset as i128)
+                    .and_then(|n| Self::try_from(n).ok())
+            }
+        }
+    )*};
+}
+
+scalar!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// A point or vector in the plane. Puzzles draw their maps with `y` growing downwards, so
+/// [`Direction4::Up`] is `(0, -1)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point or vector in space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Scalar> Point2<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Number of king moves between the points.
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Moves the point by `offset` (a direction or a [`Point2<i64>`]), or returns `None` if a
+    /// coordinate doesn't fit in `T`, for example when stepping left of `x = 0` with unsigned
+    /// coordinates.
+    pub fn step(self, offset: impl Into<Point2<i64>>) -> Option<Self> {
+        let offset = offset.into();
+        Some(Self::new(
+            self.x.checked_offset(offset.x)?,
+            self.y.checked_offset(offset.y)?,
+        ))
+    }
+
+    /// The 4-connected neighbours that can be represented in `T`.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Direction4::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(d))
+    }
+
+    /// The 8-connected neighbours that can be represented in `T`.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(d))
+    }
+}
+
+impl<T: Signed> Point2<T> {
+    /// Rotates a quarter turn clockwise around the origin, as seen on a map.
+    pub fn rotate_cw(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates a quarter turn counter-clockwise around the origin, as seen on a map.
+    pub fn rotate_ccw(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T: Scalar> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// Moves the point by `offset`, or returns `None` if a coordinate doesn't fit in `T`.
+    pub fn step(self, offset: impl Into<Point3<i64>>) -> Option<Self> {
+        let offset = offset.into();
+        Some(Self::new(
+            self.x.checked_offset(offset.x)?,
+            self.y.checked_offset(offset.y)?,
+            self.z.checked_offset(offset.z)?,
+        ))
+    }
+
+    /// The points sharing a face with this one that can be represented in `T`.
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        Direction6::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(d))
+    }
+}
+
+macro_rules! arithmetic {
+    ($point:ident { $($field:ident),* }) => {
+        impl<T: Scalar> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl<T: Scalar> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        impl<T: Scalar> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)*
+            }
+        }
+
+        impl<T: Scalar> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)*
+            }
+        }
+
+        /// Scales the vector.
+        impl<T: Scalar> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl<T: Signed> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),* }
+            }
+        }
+
+        /// Formats the point the way puzzle inputs write them, like `1,2`.
+        impl<T: Display> Display for $point<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let coordinates = [$(self.$field.to_string()),*];
+                write!(f, "{}", coordinates.join(","))
+            }
+        }
+
+        /// Parses comma separated coordinates like `1,2`.
+        impl<T: FromStr> FromStr for $point<T> {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut values = s.split(',');
+                let point = Self {$(
+                    $field: {
+                        let expected = concat!("a ", stringify!($field), " coordinate");
+                        let value = values
+                            .next()
+                            .ok_or_else(|| ParseError::missing(s, expected))?;
+                        parse_number(s, value.trim())?
+                    },
+                )*};
+                match values.next() {
+                    Some(extra) => Err(ParseError::new(s, extra, "no more coordinates")),
+                    None => Ok(point),
+                }
+            }
+        }
+    };
+}
+
+arithmetic!(Point2 { x, y });
+arithmetic!(Point3 { x, y, z });
+
+/// The four directions to the 4-connected neighbours, clockwise from up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn offset<T: Signed>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Self::Up => (T::ZERO, -T::ONE),
+            Self::Right => (T::ONE, T::ZERO),
+            Self::Down => (T::ZERO, T::ONE),
+            Self::Left => (-T::ONE, T::ZERO),
+        };
+        Point2::new(x, y)
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// The eight directions to the 8-connected neighbours, clockwise from north (up).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    pub fn offset<T: Signed>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Self::N => (T::ZERO, -T::ONE),
+            Self::NE => (T::ONE, -T::ONE),
+            Self::E => (T::ONE, T::ZERO),
+            Self::SE => (T::ONE, T::ONE),
+            Self::S => (T::ZERO, T::ONE),
+            Self::SW => (-T::ONE, T::ONE),
+            Self::W => (-T::ONE, T::ZERO),
+            Self::NW => (-T::ONE, -T::ONE),
+        };
+        Point2::new(x, y)
+    }
+
+    /// Turns an eighth of a turn clockwise.
+    pub fn rotate_cw(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns an eighth of a turn counter-clockwise.
+    pub fn rotate_ccw(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+/// The six directions to the face-sharing neighbours in space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction6 {
+    PlusX,
+    MinusX,
+    PlusY,
+    MinusY,
+    PlusZ,
+    MinusZ,
+}
+
+impl Direction6 {
+    pub const ALL: [Self; 6] = [
+        Self::PlusX,
+        Self::MinusX,
+        Self::PlusY,
+        Self::MinusY,
+        Self::PlusZ,
+        Self::MinusZ,
+    ];
+
+    pub fn offset<T: Signed>(self) -> Point3<T> {
+        let (o, i) = (T::ZERO, T::ONE);
+        match self {
+            Self::PlusX => Point3::new(i, o, o),
+            Self::MinusX => Point3::new(-i, o, o),
+            Self::PlusY => Point3::new(o, i, o),
+            Self::MinusY => Point3::new(o, -i, o),
+            Self::PlusZ => Point3::new(o, o, i),
+            Self::MinusZ => Point3::new(o, o, -i),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[self as usize ^ 1]
+    }
+}
+
+impl From<Direction4> for Point2<i64> {
+    fn from(direction: Direction4) -> Self {
+        direction.offset()
+    }
+}
+
+impl From<Direction8> for Point2<i64> {
+    fn from(direction: Direction8) -> Self {
+        direction.offset()
+    }
+}
+
+impl From<Direction6> for Point3<i64> {
+    fn from(direction: Direction6) -> Self {
+        direction.offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::new(3, -4);
+        let b = Point2::new(-1, 2);
+        assert_eq!(a + b, Point2::new(2, -2));
+        assert_eq!(a - b, Point2::new(4, -6));
+        assert_eq!(b * 3, Point2::new(-3, 6));
+        assert_eq!(-a, Point2::new(-3, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point3::new(1, 2, 3).manhattan(Point3::new(2, 2, 1)), 3);
+    }
+
+    #[test]
+    fn test_checked_step() {
+        let origin: Point2<u8> = Point2::new(0, 0);
+        assert_eq!(origin.step(Direction4::Left), None);
+        assert_eq!(origin.step(Direction4::Down), Some(Point2::new(0, 1)));
+        assert_eq!(origin.neighbors8().count(), 3);
+        assert_eq!(Point2::new(255u8, 1).step(Point2::new(1, 0)), None);
+        assert_eq!(Point3::new(1i8, 1, 1).neighbors6().count(), 6);
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Left.opposite(), Direction4::Right);
+        assert_eq!(Direction8::NW.rotate_cw(), Direction8::N);
+        assert_eq!(Direction8::from(Direction4::Down), Direction8::S);
+        assert_eq!(Direction6::MinusY.opposite(), Direction6::PlusY);
+        for d in Direction4::ALL {
+            let offset: Point2<i32> = d.offset();
+            assert_eq!(offset.rotate_cw(), d.turn_right().offset());
+            assert_eq!(offset.rotate_ccw(), d.turn_left().offset());
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2,-3".parse(), Ok(Point2::new(2, -3)));
+        assert_eq!("1,2,3".parse(), Ok(Point3::new(1u8, 2, 3)));
+        let err = "1,2".parse::<Point3<u8>>().unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (4, "a z coordinate"));
+        assert_eq!(Point3::new(1, 2, 3).to_string(), "1,2,3");
+    }
+}
@@ -0,0 +1,347 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Cost of a step in a weighted search. Implemented for every type that fits,
+/// which in practice means the integer types.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<T: Copy + Ord + Default + Add<Output = T>> Cost for T {}
+
+/// A path found by a search, from the start it left to the goal it reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S, C = usize> {
+    /// Every state on the path, both ends included.
+    pub states: Vec<S>,
+    /// Total cost of the path, the number of steps for breadth-first searches.
+    pub cost: C,
+}
+
+impl<S, C> Path<S, C> {
+    pub fn start(&self) -> &S {
+        &self.states[0]
+    }
+
+    pub fn goal(&self) -> &S {
+        &self.states[self.states.len() - 1]
+    }
+}
+
+/// A state the search reached, linked to the one it was reached from.
+struct Node<S, C> {
+    state: S,
+    parent: Option<usize>,
+    cost: C,
+}
+
+/// Follows the parents of `nodes[goal]` back to the start.
+fn unwind<S: Clone, C: Copy>(nodes: &[Node<S, C>], goal: usize) -> Path<S, C> {
+    let mut states = Vec::new();
+    let mut index = Some(goal);
+    while let Some(i) = index {
+        states.push(nodes[i].state.clone());
+        index = nodes[i].parent;
+    }
+    states.reverse();
+    Path {
+        states,
+        cost: nodes[goal].cost,
+    }
+}
+
+/// Breadth-first search that only visits one state per `key`.
+fn bfs_by<S, K, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    key: impl Fn(&S) -> K,
+) -> Option<Path<S>>
+where
+    S: Clone,
+    K: Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::new();
+    // the nodes double as the queue: everything after `next` is still to be expanded
+    let mut nodes = Vec::new();
+    for start in starts {
+        if seen.insert(key(&start)) {
+            nodes.push(Node {
+                state: start,
+                parent: None,
+                cost: 0,
+            });
+        }
+    }
+
+    let mut next = 0;
+    while next < nodes.len() {
+        if is_goal(&nodes[next].state) {
+            return Some(unwind(&nodes, next));
+        }
+        let state = nodes[next].state.clone();
+        let cost = nodes[next].cost + 1;
+        for successor in successors(&state) {
+            if seen.insert(key(&successor)) {
+                nodes.push(Node {
+                    state: successor,
+                    parent: Some(next),
+                    cost,
+                });
+            }
+        }
+        next += 1;
+    }
+    None
+}
+
+/// Finds the path with the fewest steps from any of `starts` to a state
+/// satisfying `is_goal`, where every step leads to one of the `successors` of
+/// a state.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    bfs_by(starts, successors, is_goal, S::clone)
+}
+
+/// Breadth-first search through an environment that changes every step and
+/// repeats itself every `period` steps, like day 24's blizzards.
+///
+/// `successors` gets a state and the time it is reached at, and returns the
+/// states that can be reached one step later. Waiting is only possible if it
+/// returns the state itself. States of the path are at consecutive times from
+/// `start_time` on, so the cost is the time the search took.
+pub fn bfs_over_time<S, I>(
+    start: S,
+    start_time: usize,
+    period: usize,
+    mut successors: impl FnMut(&S, usize) -> I,
+    mut is_goal: impl FnMut(&S, usize) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let path = bfs_by(
+        [(start, start_time)],
+        |(state, time)| {
+            successors(state, *time)
+                .into_iter()
+                .map(|successor| (successor, time + 1))
+                .collect::<Vec<_>>()
+        },
+        |(state, time)| is_goal(state, *time),
+        // the same state a whole period later has exactly the same future
+        |(state, time)| (state.clone(), time % period),
+    )?;
+    Some(Path {
+        states: path.states.into_iter().map(|(state, _)| state).collect(),
+        cost: path.cost,
+    })
+}
+
+/// Finds the cheapest path from any of `starts` to a state satisfying
+/// `is_goal`. `successors` returns the states a state leads to together with
+/// the cost of each step.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but explores states in order of their cost plus the
+/// `heuristic` estimate of the cost left to a goal. The path found is the
+/// cheapest one as long as the heuristic never overestimates.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut nodes: Vec<Node<S, C>> = Vec::new();
+    let mut indices: HashMap<S, usize> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    // records a way to reach `state` and queues it, unless it is already known to be reachable
+    // for less
+    let mut reach = |state: S,
+                     parent: Option<usize>,
+                     cost: C,
+                     nodes: &mut Vec<Node<S, C>>,
+                     queue: &mut BinaryHeap<Reverse<(C, C, usize)>>| {
+        let index = match indices.entry(state) {
+            Entry::Occupied(entry) => {
+                let index = *entry.get();
+                if cost >= nodes[index].cost {
+                    return;
+                }
+                nodes[index].cost = cost;
+                nodes[index].parent = parent;
+                index
+            }
+            Entry::Vacant(entry) => {
+                nodes.push(Node {
+                    state: entry.key().clone(),
+                    parent,
+                    cost,
+                });
+                *entry.insert(nodes.len() - 1)
+            }
+        };
+        let estimate = cost + heuristic(&nodes[index].state);
+        queue.push(Reverse((estimate, cost, index)));
+    };
+
+    for start in starts {
+        reach(start, None, C::default(), &mut nodes, &mut queue);
+    }
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        // a cheaper way to this state was found after this entry was queued
+        if cost > nodes[index].cost {
+            continue;
+        }
+        if is_goal(&nodes[index].state) {
+            return Some(unwind(&nodes, index));
+        }
+        let state = nodes[index].state.clone();
+        for (successor, step) in successors(&state) {
+            reach(successor, Some(index), cost + step, &mut nodes, &mut queue);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid::Position, Grid, Point2};
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#G";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE, "a wall or open ground", Some).unwrap()
+    }
+
+    fn open_neighbors(maze: &Grid<char>, position: Position) -> Vec<Position> {
+        maze.neighbors4(position)
+            .filter(|&next| maze[next] != '#')
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze();
+        let path = bfs(
+            [Point2::new(0, 0)],
+            |&p| open_neighbors(&maze, p),
+            |&p| maze[p] == 'G',
+        )
+        .unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.states.len(), 16);
+        assert_eq!(*path.goal(), Point2::new(7, 4));
+        for step in path.states.windows(2) {
+            assert_eq!(step[0].manhattan(step[1]), 1);
+        }
+
+        let walled_in = bfs([Point2::new(0, 0)], |_| Vec::new(), |&p| maze[p] == 'G');
+        assert_eq!(walled_in, None);
+    }
+
+    #[test]
+    fn test_weighted() {
+        let maze = maze();
+        // stepping onto the bottom row is expensive, so the cheapest path avoids it
+        let successors = |&p: &Position| {
+            open_neighbors(&maze, p)
+                .into_iter()
+                .map(|next| (next, if next.y == 4 { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let goal = Point2::new(7, 4);
+        let cheapest = dijkstra([Point2::new(0, 0)], successors, |&p| p == goal).unwrap();
+        assert_eq!(cheapest.cost, 24);
+        assert!(cheapest.states[..15].iter().all(|p| p.y < 4));
+
+        let guided = astar(
+            [Point2::new(0, 0)],
+            successors,
+            |&p| p.manhattan(goal),
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(guided.cost, cheapest.cost);
+    }
+
+    #[test]
+    fn test_bfs_over_time() {
+        // a corridor that closes behind the start, with a gate in the middle that opens every
+        // fourth step, a step too late to walk straight through
+        let is_open_every_fourth = |x: i32, time: usize| match x {
+            0 => time.is_multiple_of(4),
+            2 => time % 4 == 3,
+            _ => true,
+        };
+        let path = bfs_over_time(
+            0,
+            0,
+            4,
+            |&x, time| {
+                [x - 1, x, x + 1].into_iter().filter(move |&next| {
+                    (0..4).contains(&next) && is_open_every_fourth(next, time + 1)
+                })
+            },
+            |&x, _| x == 3,
+        )
+        .unwrap();
+        // the only way through waits in front of the gate for a step
+        assert_eq!(path.states, [0, 1, 1, 2, 3]);
+        assert_eq!(path.cost, 4);
+
+        // a corridor whose middle cell is only open every third step
+        let is_open = |x: i32, time: usize| x != 2 || time.is_multiple_of(3);
+
+        let late = bfs_over_time(
+            0,
+            2,
+            3,
+            |&x, time| {
+                [x, x + 1]
+                    .into_iter()
+                    .filter(move |&next| next < 5 && is_open(next, time + 1))
+            },
+            |&x, _| x == 4,
+        )
+        .unwrap();
+        // starting later means missing the gate at time 3 and waiting for the one at time 6
+        assert_eq!(late.cost, 6);
+        assert_eq!(late.states[4], 2);
+    }
+}
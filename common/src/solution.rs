@@ -0,0 +1,142 @@
+use std::{env, process};
+
+use clap::Parser;
+use tracing_subscriber::EnvFilter;
+
+use crate::{
+    input::{InputArgs, SAMPLE_PROFILE},
+    Answer, ParseError,
+};
+
+/// A solution to one day's puzzle.
+///
+/// `Default` constructs the solution with the constants used by the real puzzle
+/// input. Puzzles whose sample uses different constants (day 15 looks at row 10
+/// of the sample but row 2000000 of the real input) override [`Solution::sample`].
+pub trait Solution: Default {
+    /// Day of December the puzzle was released on.
+    const DAY: u8;
+
+    /// Number of parts the puzzle has. Only day 25 has a single part.
+    const PARTS: u8 = 2;
+
+    /// Constructs the solution with the constants used by the sample input.
+    fn sample() -> Self {
+        Self::default()
+    }
+
+    /// Constructs the solution with the constants for the named input profile.
+    fn for_profile(profile: &str) -> Self {
+        if profile == SAMPLE_PROFILE {
+            Self::sample()
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Puzzle input after parsing, shared by both parts.
+    type Input;
+
+    /// Parses the puzzle input. `aoc bench` times this separately from the parts.
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    fn problem1(&self, input: &Self::Input) -> Answer;
+
+    fn problem2(&self, input: &Self::Input) -> Answer;
+
+    /// Shows how a part of already parsed input found its answer, like the route it took, for
+    /// `aoc run --explain`. Kept apart from the parts so timing and checking them doesn't pay
+    /// for it. Most days have nothing to show.
+    fn explain(&self, _part: u8, _input: &Self::Input) -> Option<Explanation> {
+        None
+    }
+
+    /// Checks that a part can be solved on already parsed input, for input that parses but only
+    /// suits one part, like day 22's board that has to fold into a cube for part 2. Errors point
+    /// into the input like parse errors do.
+    fn check(&self, _part: u8, _input: &Self::Input) -> Result<(), ParseError> {
+        Ok(())
+    }
+
+    /// Checks and solves the given part of already parsed input.
+    fn solve_parsed(&self, part: u8, input: &Self::Input) -> Result<Answer, ParseError> {
+        self.check(part, input).map_err(|e| e.in_day(Self::DAY))?;
+        Ok(match part {
+            1 => self.problem1(input),
+            2 => self.problem2(input),
+            _ => panic!("Day {} has no part {}", Self::DAY, part),
+        })
+    }
+
+    /// Parses the input, recording the day in any error.
+    fn parse_input(&self, input: &str) -> Result<Self::Input, ParseError> {
+        self.parse(input).map_err(|e| e.in_day(Self::DAY))
+    }
+
+    /// Parses the input and solves the given part of the puzzle.
+    fn solve(&self, part: u8, input: &str) -> Result<Answer, ParseError> {
+        self.solve_parsed(part, &self.parse_input(input)?)
+    }
+}
+
+/// How a part of a puzzle found its answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    /// Shown under the answer, like the route drawn on the map.
+    pub text: String,
+    /// The same as JSON, for days that have more to it than fits in a picture.
+    pub json: Option<String>,
+}
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+/// Entry point shared by every day's binary: loads the input selected on the
+/// command line and prints the answers to each part.
+pub fn main<S: Solution>() {
+    init_tracing();
+    let args = Cli::parse().input;
+    let input = match args.load(S::DAY) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    if let Err(e) = run(&S::for_profile(&args.profile), &input) {
+        eprintln!("{}", e.diagnostic(&input, &args.source(S::DAY)));
+        process::exit(1);
+    }
+}
+
+/// Prints the `tracing` events solutions emit to stderr, but only if `RUST_LOG`
+/// asks for them (like `RUST_LOG=day19=debug`), so answers stay the only output
+/// by default.
+pub fn init_tracing() {
+    if env::var_os("RUST_LOG").is_some() {
+        tracing_subscriber::fmt()
+            .with_env_filter(EnvFilter::from_default_env())
+            .with_writer(std::io::stderr)
+            .init();
+    }
+}
+
+/// Solves every part of the puzzle and prints the answers.
+pub fn run<S: Solution>(solution: &S, input: &str) -> Result<(), ParseError> {
+    let input = solution.parse_input(input)?;
+    for part in 1..=S::PARTS {
+        print_answer(part, &solution.solve_parsed(part, &input)?);
+    }
+    Ok(())
+}
+
+/// Prints the answer to one part of a puzzle.
+pub fn print_answer(part: u8, answer: &Answer) {
+    let answer = answer.to_string();
+    // multi-line answers (like day 10's CRT picture) start on their own line
+    let separator = if answer.contains('\n') { "\n" } else { " " };
+    println!("Problem {}:{}{}", part, separator, answer);
+}
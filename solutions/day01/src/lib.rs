@@ -0,0 +1,91 @@
+use common::{parse_lines, parse_number, Answer, ParseError, Solution};
+
+/// The calories of the food each elf carries, one group of lines per elf.
+pub type CalorieGroups = Vec<Vec<u32>>;
+
+fn get_sums_iter(groups: &[Vec<u32>]) -> impl Iterator<Item = u32> + '_ {
+    groups.iter().map(|group| group.iter().sum())
+}
+
+fn get_top_three(mut top_three: [u32; 3], s: u32) -> [u32; 3] {
+    if s > top_three[0] {
+        top_three[2] = top_three[1];
+        top_three[1] = top_three[0];
+        top_three[0] = s;
+    } else if s > top_three[1] {
+        top_three[2] = top_three[1];
+        top_three[1] = s;
+    } else if s > top_three[2] {
+        top_three[2] = s;
+    }
+    top_three
+}
+
+#[derive(Default)]
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = CalorieGroups;
+
+    fn parse(&self, input: &str) -> Result<CalorieGroups, ParseError> {
+        input
+            .split("\n\n")
+            .map(|group| {
+                parse_lines(group, |line| parse_number(line, line))
+                    .map_err(|e| e.within(input, group))
+            })
+            .collect()
+    }
+
+    fn problem1(&self, groups: &CalorieGroups) -> Answer {
+        most_calories(groups).into()
+    }
+
+    fn problem2(&self, groups: &CalorieGroups) -> Answer {
+        top_three_calories(groups).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    most_calories(&Day01.parse(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u32 {
+    top_three_calories(&Day01.parse(input).unwrap())
+}
+
+fn most_calories(groups: &[Vec<u32>]) -> u32 {
+    get_sums_iter(groups).max().unwrap_or(0)
+}
+
+fn top_three_calories(groups: &[Vec<u32>]) -> u32 {
+    get_sums_iter(groups)
+        .fold([0; 3], get_top_three)
+        .into_iter()
+        .sum()
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 24000);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 45000);
+}
+
+#[test]
+fn test_parse_error() {
+    let err = Day01.parse("1000\n2000\n\n3000\nA Q\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 5, column 1: expected a number, found `A Q`"
+    );
+}
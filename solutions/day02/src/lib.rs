@@ -0,0 +1,143 @@
+use common::{parse_lines, Answer, ParseError, Solution};
+use std::str::FromStr;
+
+const ROCK: u32 = 1;
+const PAPER: u32 = 2;
+const SCISSORS: u32 = 3;
+const LOSE: u32 = 0;
+const TIE: u32 = 3;
+const WIN: u32 = 6;
+
+/// What the opponent plays: rock, paper or scissors.
+#[derive(Clone, Copy, Debug)]
+pub enum Opponent {
+    A,
+    B,
+    C,
+}
+
+/// The second column of the strategy guide, which each part reads differently.
+#[derive(Clone, Copy, Debug)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+/// One line of the strategy guide.
+#[derive(Clone, Copy, Debug)]
+pub struct Round {
+    opponent: Opponent,
+    column: Column,
+}
+
+impl FromStr for Round {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (opponent, column) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::missing(s, "a space, then `X`, `Y` or `Z`"))?;
+        let opponent = match opponent {
+            "A" => Opponent::A,
+            "B" => Opponent::B,
+            "C" => Opponent::C,
+            _ => return Err(ParseError::new(s, opponent, "`A`, `B` or `C`")),
+        };
+        let column = match column {
+            "X" => Column::X,
+            "Y" => Column::Y,
+            "Z" => Column::Z,
+            _ => return Err(ParseError::new(s, column, "`X`, `Y` or `Z`")),
+        };
+        Ok(Round { opponent, column })
+    }
+}
+
+#[derive(Default)]
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Round>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Round>, ParseError> {
+        parse_lines(input, str::parse)
+    }
+
+    fn problem1(&self, rounds: &Vec<Round>) -> Answer {
+        score_as_shapes(rounds).into()
+    }
+
+    fn problem2(&self, rounds: &Vec<Round>) -> Answer {
+        score_as_outcomes(rounds).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    score_as_shapes(&Day02.parse(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u32 {
+    score_as_outcomes(&Day02.parse(input).unwrap())
+}
+
+/// The total score if the second column is the shape to play.
+fn score_as_shapes(rounds: &[Round]) -> u32 {
+    rounds
+        .iter()
+        .map(|round| match (round.opponent, round.column) {
+            (Opponent::A, Column::X) => ROCK + TIE,
+            (Opponent::B, Column::X) => ROCK + LOSE,
+            (Opponent::C, Column::X) => ROCK + WIN,
+            (Opponent::A, Column::Y) => PAPER + WIN,
+            (Opponent::B, Column::Y) => PAPER + TIE,
+            (Opponent::C, Column::Y) => PAPER + LOSE,
+            (Opponent::A, Column::Z) => SCISSORS + LOSE,
+            (Opponent::B, Column::Z) => SCISSORS + WIN,
+            (Opponent::C, Column::Z) => SCISSORS + TIE,
+        })
+        .sum()
+}
+
+/// The total score if the second column is how the round has to end.
+fn score_as_outcomes(rounds: &[Round]) -> u32 {
+    rounds
+        .iter()
+        .map(|round| match (round.opponent, round.column) {
+            (Opponent::A, Column::X) => LOSE + SCISSORS,
+            (Opponent::B, Column::X) => LOSE + ROCK,
+            (Opponent::C, Column::X) => LOSE + PAPER,
+            (Opponent::A, Column::Y) => TIE + ROCK,
+            (Opponent::B, Column::Y) => TIE + PAPER,
+            (Opponent::C, Column::Y) => TIE + SCISSORS,
+            (Opponent::A, Column::Z) => WIN + PAPER,
+            (Opponent::B, Column::Z) => WIN + SCISSORS,
+            (Opponent::C, Column::Z) => WIN + ROCK,
+        })
+        .sum()
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 15);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 12);
+}
+
+#[test]
+fn test_parse_error() {
+    let err = Day02.parse("A Y\nA Q\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 3: expected `X`, `Y` or `Z`, found `Q`"
+    );
+}
@@ -0,0 +1,146 @@
+mod prority;
+
+use common::{parse_lines, Answer, ParseError, Solution};
+use std::{collections::HashSet, str::FromStr};
+
+/// A rucksack's items, by priority, with the one item in both of its compartments.
+pub struct Rucksack {
+    items: HashSet<u32>,
+    shared: u32,
+}
+
+impl FromStr for Rucksack {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let items = s
+            .char_indices()
+            .map(|(i, c)| {
+                prority::char_to_priority(c).ok_or_else(|| {
+                    let item = &s[i..i + c.len_utf8()];
+                    ParseError::new(s, item, "an item from `a` to `z` or `A` to `Z`")
+                })
+            })
+            .collect::<Result<Vec<u32>, ParseError>>()?;
+        if items.len() % 2 == 1 {
+            return Err(ParseError::missing(s, "an even number of items"));
+        }
+        let (compartment1, compartment2) = items.split_at(items.len() / 2);
+        let compartment1: HashSet<u32> = compartment1.iter().copied().collect();
+        let shared = *compartment2
+            .iter()
+            .find(|item| compartment1.contains(item))
+            .ok_or_else(|| ParseError::new(s, s, "an item in both compartments"))?;
+        Ok(Rucksack {
+            items: items.into_iter().collect(),
+            shared,
+        })
+    }
+}
+
+/// Every rucksack, with the badge of each group of three elves.
+pub struct Rucksacks {
+    rucksacks: Vec<Rucksack>,
+    badges: Vec<u32>,
+}
+
+#[derive(Default)]
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Rucksacks;
+
+    fn parse(&self, input: &str) -> Result<Rucksacks, ParseError> {
+        let rucksacks: Vec<Rucksack> = parse_lines(input, str::parse)?;
+        let lines: Vec<&str> = input.lines().collect();
+        let badges = rucksacks
+            .chunks(3)
+            .zip(lines.chunks(3))
+            .map(|(group, lines)| match group {
+                [a, b, c] => a
+                    .items
+                    .iter()
+                    .find(|item| b.items.contains(item) && c.items.contains(item))
+                    .copied()
+                    .ok_or_else(|| {
+                        ParseError::new(
+                            input,
+                            lines[0],
+                            "a group with an item in all three rucksacks",
+                        )
+                    }),
+                _ => Err(ParseError::missing(input, "three rucksacks in every group")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Rucksacks { rucksacks, badges })
+    }
+
+    fn problem1(&self, rucksacks: &Rucksacks) -> Answer {
+        shared_priorities(rucksacks).into()
+    }
+
+    fn problem2(&self, rucksacks: &Rucksacks) -> Answer {
+        badge_priorities(rucksacks).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    shared_priorities(&Day03.parse(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u32 {
+    badge_priorities(&Day03.parse(input).unwrap())
+}
+
+fn shared_priorities(rucksacks: &Rucksacks) -> u32 {
+    rucksacks
+        .rucksacks
+        .iter()
+        .map(|rucksack| rucksack.shared)
+        .sum()
+}
+
+fn badge_priorities(rucksacks: &Rucksacks) -> u32 {
+    rucksacks.badges.iter().sum()
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 157);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 70);
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |input: &str| Day03.parse(input).err().unwrap().to_string();
+    assert_eq!(
+        error("abca\nA Q\n"),
+        "line 2, column 2: expected an item from `a` to `z` or `A` to `Z`, found ` `"
+    );
+    assert_eq!(
+        error("abcab\n"),
+        "line 1, column 6: expected an even number of items, found the end of the line"
+    );
+    assert_eq!(
+        error("abcd\n"),
+        "line 1, column 1: expected an item in both compartments, found `abcd`"
+    );
+    assert_eq!(
+        error("aa\nbb\n"),
+        "line 3, column 1: expected three rucksacks in every group, found the end of the line"
+    );
+    assert_eq!(
+        error("aa\nbb\ncc\n"),
+        "line 1, column 1: expected a group with an item in all three rucksacks, found `aa`"
+    );
+}
@@ -1,7 +1,7 @@
 // I could probably do some ascii mathz, but I feel like
 // the compiler could optimize a match statement better?
-pub fn char_to_priority(c: char) -> u32 {
-    match c {
+pub fn char_to_priority(c: char) -> Option<u32> {
+    Some(match c {
         'a' => 1,
         'b' => 2,
         'c' => 3,
@@ -54,6 +54,6 @@ pub fn char_to_priority(c: char) -> u32 {
         'X' => 50,
         'Y' => 51,
         'Z' => 52,
-        _ => unreachable!("Invalid input"),
-    }
+        _ => return None,
+    })
 }
@@ -0,0 +1,110 @@
+use common::{parse_lines, parse_number, Answer, ParseError, Solution};
+use std::ops::RangeInclusive;
+
+/// The sections two elves are assigned to clean.
+pub type RangePair = (RangeInclusive<u32>, RangeInclusive<u32>);
+
+fn parse_range(text: &str, range: &str) -> Result<RangeInclusive<u32>, ParseError> {
+    let (start, end) = range.split_once('-').ok_or_else(|| {
+        ParseError::missing(range, "`-` and the last section").within(text, range)
+    })?;
+    let (start, end): (u32, u32) = (parse_number(text, start)?, parse_number(text, end)?);
+    if end < start {
+        return Err(ParseError::new(
+            text,
+            range,
+            "a range that doesn't end before it starts",
+        ));
+    }
+    Ok(start..=end)
+}
+
+fn parse_range_pair(line: &str) -> Result<RangePair, ParseError> {
+    let (first, second) = line
+        .split_once(',')
+        .ok_or_else(|| ParseError::missing(line, "`,` and a second range"))?;
+    Ok((parse_range(line, first)?, parse_range(line, second)?))
+}
+
+#[derive(Default)]
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<RangePair>;
+
+    fn parse(&self, input: &str) -> Result<Vec<RangePair>, ParseError> {
+        parse_lines(input, parse_range_pair)
+    }
+
+    fn problem1(&self, pairs: &Vec<RangePair>) -> Answer {
+        count_containing(pairs).into()
+    }
+
+    fn problem2(&self, pairs: &Vec<RangePair>) -> Answer {
+        count_overlapping(pairs).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    count_containing(&Day04.parse(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u32 {
+    count_overlapping(&Day04.parse(input).unwrap())
+}
+
+/// How many pairs have one range inside the other.
+fn count_containing(pairs: &[RangePair]) -> u32 {
+    pairs
+        .iter()
+        .filter(|(r1, r2)| {
+            (r1.start() <= r2.start() && r1.end() >= r2.end())
+                || (r2.start() <= r1.start() && r2.end() >= r1.end())
+        })
+        .count() as u32
+}
+
+/// How many pairs have ranges with any sections in common.
+fn count_overlapping(pairs: &[RangePair]) -> u32 {
+    pairs
+        .iter()
+        .filter(|(r1, r2)| r1.start() <= r2.end() && r2.start() <= r1.end())
+        .count() as u32
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 2);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 4);
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |input: &str| Day04.parse(input).unwrap_err().to_string();
+    assert_eq!(
+        error("2-4,6-8\nA Q\n"),
+        "line 2, column 4: expected `,` and a second range, found the end of the line"
+    );
+    assert_eq!(
+        error("2-4,6-x\n"),
+        "line 1, column 7: expected a number, found `x`"
+    );
+    assert_eq!(
+        error("2-4,6\n"),
+        "line 1, column 6: expected `-` and the last section, found the end of the line"
+    );
+    assert_eq!(
+        error("4-2,6-8\n"),
+        "line 1, column 1: expected a range that doesn't end before it starts, found `4-2`"
+    );
+}
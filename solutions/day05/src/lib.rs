@@ -0,0 +1,204 @@
+use common::{parse_number, Answer, ParseError, Solution};
+
+/// Moving some crates from the top of one stack to another, with the stacks counted from 0.
+#[derive(Clone, Copy, Debug)]
+pub struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+/// The starting stacks of crates, bottom first, and the moves the crane makes.
+#[derive(Clone, Debug)]
+pub struct Crates {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<Move>,
+}
+
+/// Reads the drawing of the stacks, whose last line numbers them.
+fn get_stacks(stacks_str: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut lines: Vec<&str> = stacks_str.lines().collect();
+    let labels = lines.pop().unwrap_or_default();
+    let mut num_columns = 0;
+    for label in labels.split_whitespace() {
+        num_columns += 1;
+        if label != num_columns.to_string() {
+            return Err(ParseError::new(labels, label, format!("`{}`", num_columns))
+                .within(stacks_str, labels));
+        }
+    }
+    if num_columns == 0 {
+        return Err(
+            ParseError::missing(labels, "the numbers of the stacks").within(stacks_str, labels)
+        );
+    }
+    let mut stacks: Vec<Vec<char>> = vec![vec![]; num_columns];
+
+    for line in lines.into_iter().rev() {
+        let chars: Vec<(usize, char)> = line.char_indices().collect();
+        for (i, cell) in chars.chunks(4).enumerate() {
+            let start = cell[0].0;
+            let end = cell.last().map_or(start, |&(j, c)| j + c.len_utf8());
+            let token = &line[start..end];
+            let error =
+                |expected: &str| ParseError::new(line, token, expected).within(stacks_str, line);
+            match *cell {
+                [(_, '['), (_, c), (_, ']')] | [(_, '['), (_, c), (_, ']'), (_, ' ')]
+                    if c != ' ' =>
+                {
+                    if i >= num_columns {
+                        return Err(error("a crate above a numbered stack"));
+                    }
+                    stacks[i].push(c);
+                }
+                _ if cell.iter().all(|&(_, c)| c == ' ') => {}
+                _ => return Err(error("a crate like `[A]` or spaces")),
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+fn line_to_move_instruction(line: &str) -> Result<(usize, &str, &str, &str), ParseError> {
+    let words: Vec<&str> = line.split(' ').collect();
+    match words[..] {
+        ["move", count, "from", from, "to", to] => {
+            Ok((parse_number(line, count)?, count, from, to))
+        }
+        _ => Err(ParseError::new(
+            line,
+            line,
+            "`move <number> from <stack> to <stack>`",
+        )),
+    }
+}
+
+/// Reads the moves, checking that they only use numbered stacks and never take more crates
+/// than a stack holds.
+fn get_moves(moves_str: &str, stacks: &[Vec<char>]) -> Result<Vec<Move>, ParseError> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    let expected_stack = format!("a stack from 1 to {}", stacks.len());
+    let mut moves = vec![];
+    for line in moves_str.lines() {
+        let error = |token: &str, expected: &str| {
+            ParseError::new(line, token, expected).within(moves_str, line)
+        };
+        let (count, count_str, from, to) =
+            line_to_move_instruction(line).map_err(|e| e.within(moves_str, line))?;
+        let stack = |token: &str| match parse_number::<usize>(line, token) {
+            Ok(n) if (1..=stacks.len()).contains(&n) => Ok(n - 1),
+            _ => Err(error(token, &expected_stack)),
+        };
+        let (from, to) = (stack(from)?, stack(to)?);
+        if count > heights[from] {
+            let expected = format!("at most the {} crates on the stack", heights[from]);
+            return Err(error(count_str, &expected));
+        }
+        heights[from] -= count;
+        heights[to] += count;
+        moves.push(Move { count, from, to });
+    }
+    Ok(moves)
+}
+
+fn solve_problem(crates: &Crates, preserve_order: bool) -> String {
+    let mut stacks = crates.stacks.clone();
+
+    for &Move { count, from, to } in &crates.moves {
+        let height = stacks[from].len();
+        let boxes_to_push = stacks[from].split_off(height - count);
+
+        if preserve_order {
+            stacks[to].extend(boxes_to_push);
+        } else {
+            stacks[to].extend(boxes_to_push.into_iter().rev());
+        }
+    }
+
+    stacks
+        .into_iter()
+        .filter_map(|mut stack| stack.pop())
+        .collect()
+}
+
+#[derive(Default)]
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Crates;
+
+    fn parse(&self, input: &str) -> Result<Crates, ParseError> {
+        let (stacks_str, moves_str) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::missing(input, "a blank line, then the moves"))?;
+        let stacks = get_stacks(stacks_str).map_err(|e| e.within(input, stacks_str))?;
+        let moves = get_moves(moves_str, &stacks).map_err(|e| e.within(input, moves_str))?;
+        Ok(Crates { stacks, moves })
+    }
+
+    fn problem1(&self, crates: &Crates) -> Answer {
+        solve_problem(crates, false).into()
+    }
+
+    fn problem2(&self, crates: &Crates) -> Answer {
+        solve_problem(crates, true).into()
+    }
+}
+
+pub fn problem1(input: &str) -> String {
+    solve_problem(&Day05.parse(input).unwrap(), false)
+}
+
+pub fn problem2(input: &str) -> String {
+    solve_problem(&Day05.parse(input).unwrap(), true)
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, "CMZ".to_string());
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, "MCD".to_string());
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |input: &str| Day05.parse(input).unwrap_err().to_string();
+    assert_eq!(
+        error("A Q\n"),
+        "line 2, column 1: expected a blank line, then the moves, found the end of the line"
+    );
+    assert_eq!(
+        error("[A] [B]\n 1   3\n\n"),
+        "line 2, column 6: expected `2`, found `3`"
+    );
+    assert_eq!(
+        error("[A] (B)\n 1   2\n\n"),
+        "line 1, column 5: expected a crate like `[A]` or spaces, found `(B)`"
+    );
+    assert_eq!(
+        error("[A] [B]\n 1\n\n"),
+        "line 1, column 5: expected a crate above a numbered stack, found `[B]`"
+    );
+    assert_eq!(
+        error("[A] [B]\n 1   2\n\nmove 1 from 2 to 1\nmove 1 from 1 to 3\n"),
+        "line 5, column 18: expected a stack from 1 to 2, found `3`"
+    );
+    assert_eq!(
+        error("[A] [B]\n 1   2\n\nmove 1 from 2 to 1\nmove 2 from 2 to 1\n"),
+        "line 5, column 6: expected at most the 0 crates on the stack, found `2`"
+    );
+    assert_eq!(
+        error("[A] [B]\n 1   2\n\nmove one from 2 to 1\n"),
+        "line 4, column 6: expected a number, found `one`"
+    );
+}
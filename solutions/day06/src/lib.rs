@@ -0,0 +1,101 @@
+use common::{Answer, ParseError, Solution};
+
+const PACKET_MARKER_SIZE: usize = 4;
+const MESSAGE_MARKER_SIZE: usize = 14;
+
+/// The datastream from the device, checked to have a start-of-message marker, which is also
+/// a start-of-packet marker.
+pub struct Signal(String);
+
+fn find_marker(signal: &str, num_distinct_chars: usize) -> Option<usize> {
+    signal
+        .as_bytes()
+        .windows(num_distinct_chars)
+        .position(|window| {
+            // hyperfine -w 10 -N ./target/release/day06
+            // yields 2.3 ms ± 0.2 ms for below solutions
+            // versus 3.4 ms ± 0.2 using a set to check equality
+            window
+                .iter()
+                .enumerate()
+                .all(|(i, c)| window.iter().skip(i + 1).find(|d| c == *d).is_none())
+        })
+        .map(|i| i + num_distinct_chars)
+}
+
+fn solve_problem(signal: &Signal, num_distinct_chars: usize) -> usize {
+    find_marker(&signal.0, num_distinct_chars).expect("parsing checks for a message marker")
+}
+
+#[derive(Default)]
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Signal;
+
+    fn parse(&self, input: &str) -> Result<Signal, ParseError> {
+        let mut lines = input.lines();
+        let signal = lines.next().unwrap_or_default();
+        if let Some(line) = lines.find(|line| !line.is_empty()) {
+            return Err(ParseError::new(input, line, "the signal to be on one line"));
+        }
+        if let Some((i, c)) = signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            let token = &signal[i..i + c.len_utf8()];
+            return Err(ParseError::new(input, token, "a letter from `a` to `z`"));
+        }
+        if find_marker(signal, MESSAGE_MARKER_SIZE).is_none() {
+            let expected = format!("{} different letters in a row", MESSAGE_MARKER_SIZE);
+            return Err(ParseError::missing(signal, expected));
+        }
+        Ok(Signal(signal.to_string()))
+    }
+
+    fn problem1(&self, signal: &Signal) -> Answer {
+        solve_problem(signal, PACKET_MARKER_SIZE).into()
+    }
+
+    fn problem2(&self, signal: &Signal) -> Answer {
+        solve_problem(signal, MESSAGE_MARKER_SIZE).into()
+    }
+}
+
+pub fn problem1(input: &str) -> usize {
+    solve_problem(&Day06.parse(input).unwrap(), PACKET_MARKER_SIZE)
+}
+
+pub fn problem2(input: &str) -> usize {
+    solve_problem(&Day06.parse(input).unwrap(), MESSAGE_MARKER_SIZE)
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 7);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 19);
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |input: &str| Day06.parse(input).err().unwrap().to_string();
+    assert_eq!(
+        error("A Q\n"),
+        "line 1, column 1: expected a letter from `a` to `z`, found `A`"
+    );
+    assert_eq!(
+        error("abcd\nefgh\n"),
+        "line 2, column 1: expected the signal to be on one line, found `efgh`"
+    );
+    assert_eq!(
+        error("abcdefghijklmabcdefghijklm\n"),
+        "line 1, column 27: expected 14 different letters in a row, found the end of the line"
+    );
+}
@@ -0,0 +1,260 @@
+use common::{parse_lines, parse_number, Answer, ParseError, Solution};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, str::FromStr};
+
+const FILESYSTEM_SIZE: u64 = 70000000;
+const UNUSED_SPACE_NEEDED: u64 = 30000000;
+
+/// A line of the terminal output: a command, or something `ls` printed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Entry {
+    CdRoot,
+    CdUp,
+    Cd(String),
+    Ls,
+    Dir(String),
+    File(String, u64),
+}
+
+impl FromStr for Entry {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.split(' ').collect::<Vec<&str>>()[..] {
+            ["$", "cd", ".."] => Entry::CdUp,
+            ["$", "cd", "/"] => Entry::CdRoot,
+            ["$", "cd", x] => Entry::Cd(x.to_string()),
+            ["$", "ls"] => Entry::Ls,
+            ["$", command, ..] => {
+                return Err(ParseError::new(s, command, "`cd <directory>` or `ls`"));
+            }
+            ["dir", d] => Entry::Dir(d.to_string()),
+            [size, name] => Entry::File(name.to_string(), parse_number(s, size)?),
+            _ => {
+                return Err(ParseError::new(
+                    s,
+                    s,
+                    "a command, `dir <name>` or `<size> <name>`",
+                ))
+            }
+        })
+    }
+}
+
+/// Checks that every line happens in a directory: the log has to start at `/`, and can't go up
+/// from there.
+fn check_log(input: &str, log: &[Entry]) -> Result<(), ParseError> {
+    let mut depth = None;
+    for (line, entry) in input.lines().zip(log) {
+        depth = match (entry, depth) {
+            (Entry::CdRoot, _) => Some(0),
+            (Entry::CdUp, Some(0)) => {
+                return Err(ParseError::new(input, line, "a directory to go up from"));
+            }
+            (Entry::CdUp, Some(d)) => Some(d - 1),
+            (Entry::Cd(_), Some(d)) => Some(d + 1),
+            (_, Some(d)) => Some(d),
+            (_, None) => return Err(ParseError::new(input, line, "`$ cd /` first")),
+        };
+    }
+    Ok(())
+}
+
+#[allow(dead_code)]
+struct File<'a> {
+    name: &'a str,
+    size: u64,
+}
+
+#[allow(dead_code)]
+struct Directory<'a> {
+    name: &'a str,
+    directories: HashMap<&'a str, Rc<RefCell<Directory<'a>>>>,
+    files: Vec<File<'a>>,
+}
+
+impl<'a> File<'a> {
+    #[allow(dead_code)]
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl<'a> Directory<'a> {
+    fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            directories: HashMap::new(),
+            files: vec![],
+        }
+    }
+
+    #[allow(dead_code)]
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn directory_sizes(&self) -> u64 {
+        self.directories.values().map(|d| d.borrow().size()).sum()
+    }
+
+    fn file_sizes(&self) -> u64 {
+        self.files.iter().map(|f| f.size()).sum()
+    }
+
+    fn size(&self) -> u64 {
+        self.directory_sizes() + self.file_sizes()
+    }
+
+    fn sum_under_n(&self, n: u64) -> u64 {
+        self.recursive_directory_sizes()
+            .into_iter()
+            .filter(|s| s <= &n)
+            .sum()
+    }
+
+    fn sizes_over_n(&self, n: u64) -> Vec<u64> {
+        self.recursive_directory_sizes()
+            .into_iter()
+            .filter(|s| s >= &n)
+            .collect()
+    }
+
+    fn recursive_directory_sizes(&self) -> Vec<u64> {
+        let mut sizes: Vec<u64> = self
+            .directories
+            .values()
+            .flat_map(|d| d.borrow().recursive_directory_sizes())
+            .collect();
+
+        sizes.push(self.size());
+        sizes
+    }
+}
+
+fn get_root_dir_from_log(log: &[Entry]) -> Rc<RefCell<Directory<'_>>> {
+    let root_dir = Rc::new(RefCell::new(Directory::new("/")));
+    let mut directories: Vec<Rc<RefCell<Directory>>> = vec![];
+
+    // parsing checks every entry has a directory to happen in
+    for entry in log {
+        match entry {
+            Entry::CdUp => {
+                directories.pop();
+            }
+            Entry::CdRoot => {
+                directories.clear();
+                directories.push(root_dir.clone());
+            }
+            Entry::Cd(x) => {
+                let directory = directories.last().unwrap().clone();
+                let mut y = directory.borrow_mut();
+                let cd_dir = y
+                    .directories
+                    .entry(x)
+                    .or_insert(Rc::new(RefCell::new(Directory::new(x))));
+                directories.push(cd_dir.clone());
+            }
+            Entry::Ls => {}
+            Entry::Dir(d) => {
+                let directory = directories.last_mut().unwrap();
+                directory
+                    .borrow_mut()
+                    .directories
+                    .insert(d, Rc::new(RefCell::new(Directory::new(d))));
+            }
+            Entry::File(name, size) => {
+                let directory = directories.last_mut().unwrap();
+                let file = File { name, size: *size };
+                directory.borrow_mut().files.push(file);
+            }
+        }
+    }
+
+    root_dir
+}
+
+#[derive(Default)]
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<Entry>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Entry>, ParseError> {
+        let log = parse_lines(input, str::parse)?;
+        check_log(input, &log)?;
+        Ok(log)
+    }
+
+    fn problem1(&self, log: &Vec<Entry>) -> Answer {
+        sum_of_small_directories(log).into()
+    }
+
+    fn problem2(&self, log: &Vec<Entry>) -> Answer {
+        smallest_directory_to_delete(log).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u64 {
+    sum_of_small_directories(&Day07.parse(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u64 {
+    smallest_directory_to_delete(&Day07.parse(input).unwrap())
+}
+
+fn sum_of_small_directories(log: &[Entry]) -> u64 {
+    get_root_dir_from_log(log).borrow().sum_under_n(100000)
+}
+
+fn smallest_directory_to_delete(log: &[Entry]) -> u64 {
+    let root_dir_cell = get_root_dir_from_log(log);
+    let root_dir = root_dir_cell.borrow();
+    let space_to_free = (root_dir.size() + UNUSED_SPACE_NEEDED).saturating_sub(FILESYSTEM_SIZE);
+    // the root directory is always big enough
+    root_dir
+        .sizes_over_n(space_to_free)
+        .into_iter()
+        .min()
+        .unwrap_or(root_dir.size())
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 95437);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 24933642);
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |input: &str| Day07.parse(input).unwrap_err().to_string();
+    assert_eq!(
+        error("$ cd /\nA Q\n"),
+        "line 2, column 1: expected a number, found `A`"
+    );
+    assert_eq!(
+        error("$ cd /\n$ rm -rf\n"),
+        "line 2, column 3: expected `cd <directory>` or `ls`, found `rm`"
+    );
+    assert_eq!(
+        error("$ ls\n"),
+        "line 1, column 1: expected `$ cd /` first, found `$ ls`"
+    );
+    assert_eq!(
+        error("$ cd /\n$ cd ..\n"),
+        "line 2, column 1: expected a directory to go up from, found `$ cd ..`"
+    );
+}
@@ -0,0 +1,96 @@
+use common::{grid::Position, Answer, Grid, ParseError, Point2, Solution};
+
+fn parse_trees(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(input, "a tree height", |c| c.to_digit(10).map(|d| d as u8))
+}
+
+fn is_tree_visible(grid: &Grid<u8>, position: Position) -> bool {
+    let Point2 { x, y } = position;
+    let tree = grid[position];
+    let row = grid.row(y);
+
+    row[..x].iter().all(|&t| t < tree)
+        || row[x + 1..].iter().all(|&t| t < tree)
+        || grid.column(x).take(y).all(|&t| t < tree)
+        || grid.column(x).skip(y + 1).all(|&t| t < tree)
+}
+
+/// Number of trees that can be seen looking along `line`, up to and including the first one at
+/// least as tall as `tree`.
+fn viewing_distance<'a>(tree: u8, line: impl Iterator<Item = &'a u8>) -> u32 {
+    let mut distance = 0;
+    for &t in line {
+        distance += 1;
+        if t >= tree {
+            break;
+        }
+    }
+    distance
+}
+
+fn tree_scenic_score(grid: &Grid<u8>, position: Position) -> u32 {
+    let Point2 { x, y } = position;
+    let tree = grid[position];
+    let row = grid.row(y);
+
+    viewing_distance(tree, row[..x].iter().rev())
+        * viewing_distance(tree, row[x + 1..].iter())
+        * viewing_distance(tree, grid.column(x).take(y).rev())
+        * viewing_distance(tree, grid.column(x).skip(y + 1))
+}
+
+fn num_visible_trees(grid: &Grid<u8>) -> u32 {
+    grid.positions()
+        .filter(|&position| is_tree_visible(grid, position))
+        .count() as u32
+}
+
+fn max_scenic_score(grid: &Grid<u8>) -> u32 {
+    grid.positions()
+        .map(|position| tree_scenic_score(grid, position))
+        .max()
+        .unwrap_or(0)
+}
+
+#[derive(Default)]
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Grid<u8>;
+
+    fn parse(&self, input: &str) -> Result<Grid<u8>, ParseError> {
+        parse_trees(input)
+    }
+
+    fn problem1(&self, grid: &Grid<u8>) -> Answer {
+        num_visible_trees(grid).into()
+    }
+
+    fn problem2(&self, grid: &Grid<u8>) -> Answer {
+        max_scenic_score(grid).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    num_visible_trees(&parse_trees(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u32 {
+    max_scenic_score(&parse_trees(input).unwrap())
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 21);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 8);
+}
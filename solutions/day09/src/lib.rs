@@ -0,0 +1,130 @@
+use common::{parse_lines, parse_number, Answer, Direction4, ParseError, Point2, Solution};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+fn parse_direction(s: &str) -> Result<Direction4, ParseError> {
+    match s {
+        "U" => Ok(Direction4::Up),
+        "D" => Ok(Direction4::Down),
+        "L" => Ok(Direction4::Left),
+        "R" => Ok(Direction4::Right),
+        _ => Err(ParseError::new(s, s, "one of U, D, L or R")),
+    }
+}
+
+pub struct Motion {
+    direction: Direction4,
+    steps: usize,
+}
+
+impl FromStr for Motion {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir_str, steps_str) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::missing(s, "a direction and a number of steps"))?;
+        Ok(Self {
+            direction: parse_direction(dir_str).map_err(|e| e.within(s, dir_str))?,
+            steps: parse_number(s, steps_str)?,
+        })
+    }
+}
+
+type Coordinate = Point2<isize>;
+
+/// Moves `knot` one step towards `other`, diagonally if they aren't in the same row or column.
+fn follow(knot: &mut Coordinate, other: &Coordinate) {
+    let d = *other - *knot;
+    *knot += Point2::new(d.x.signum(), d.y.signum());
+}
+
+struct Simulation {
+    nodes: Vec<Coordinate>,
+    tail_positions: HashSet<Coordinate>,
+}
+
+impl Simulation {
+    fn new(num_nodes: usize) -> Self {
+        let mut tail_positions = HashSet::new();
+        tail_positions.insert(Coordinate::default());
+        let nodes = vec![Coordinate::default(); num_nodes];
+        Self {
+            nodes,
+            tail_positions,
+        }
+    }
+
+    fn move_once(&mut self, dir: Direction4) {
+        let mut iter = self.nodes.iter_mut();
+
+        let mut previous = iter.next().unwrap();
+        *previous += dir.offset();
+
+        for node in iter {
+            if previous.chebyshev(*node) <= 1 {
+                return;
+            }
+            follow(node, previous);
+            previous = node;
+        }
+
+        self.tail_positions.insert(*previous);
+    }
+
+    fn simulate_movement(&mut self, motion: &Motion) {
+        for _ in 0..motion.steps {
+            self.move_once(motion.direction);
+        }
+    }
+
+    fn run(&mut self, motions: &[Motion]) -> u32 {
+        motions.iter().for_each(|motion| {
+            self.simulate_movement(motion);
+        });
+        self.tail_positions.len() as u32
+    }
+}
+
+#[derive(Default)]
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Motion>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Motion>, ParseError> {
+        parse_lines(input, str::parse)
+    }
+
+    fn problem1(&self, motions: &Vec<Motion>) -> Answer {
+        Simulation::new(2).run(motions).into()
+    }
+
+    fn problem2(&self, motions: &Vec<Motion>) -> Answer {
+        Simulation::new(10).run(motions).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    Simulation::new(2).run(&Day09.parse(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u32 {
+    Simulation::new(10).run(&Day09.parse(input).unwrap())
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 13);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 1);
+}
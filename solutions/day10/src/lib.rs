@@ -0,0 +1,143 @@
+use common::{parse_lines, parse_number, Answer, ParseError, Solution};
+
+const CRT_SCREEN_SIZE: usize = 240;
+
+#[derive(Clone, Copy)]
+pub enum Instruction {
+    Noop,
+    Addx(isize),
+}
+
+use std::str::FromStr;
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(' ') {
+            Some(("addx", x)) => Ok(Instruction::Addx(parse_number(s, x)?)),
+            None if s == "noop" => Ok(Instruction::Noop),
+            _ => {
+                let token = s.split(' ').next().unwrap_or(s);
+                Err(ParseError::new(s, token, "`noop` or `addx <number>`"))
+            }
+        }
+    }
+}
+
+struct Cpu {
+    x: isize,
+    x_history: [isize; CRT_SCREEN_SIZE],
+    image: [char; CRT_SCREEN_SIZE],
+    pixel_to_draw: usize,
+}
+
+impl Cpu {
+    fn new() -> Self {
+        Self {
+            x: 1,
+            x_history: [1; CRT_SCREEN_SIZE],
+            image: ['.'; CRT_SCREEN_SIZE],
+            pixel_to_draw: 0,
+        }
+    }
+
+    fn draw_sprite(&mut self) {
+        if (self.x - 1..=self.x + 1).contains(&(self.pixel_to_draw as isize % 40)) {
+            self.image[self.pixel_to_draw] = '#'
+        }
+        self.pixel_to_draw += 1;
+    }
+    fn process_instruction(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::Noop => {
+                self.draw_sprite();
+                self.record_x();
+            }
+            Instruction::Addx(x) => {
+                self.draw_sprite();
+                self.record_x();
+                self.draw_sprite();
+                self.x += x;
+                self.record_x()
+            }
+        }
+    }
+
+    fn record_x(&mut self) {
+        self.x_history[self.pixel_to_draw - 1] = self.x;
+    }
+
+    fn process_program(&mut self, program: &[Instruction]) {
+        program
+            .iter()
+            .for_each(|&instruction| self.process_instruction(instruction));
+    }
+}
+
+#[derive(Default)]
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>, ParseError> {
+        parse_lines(input, str::parse)
+    }
+
+    fn problem1(&self, program: &Vec<Instruction>) -> Answer {
+        signal_strength(program).into()
+    }
+
+    fn problem2(&self, program: &Vec<Instruction>) -> Answer {
+        render_crt(program).into()
+    }
+}
+
+pub fn problem1(input: &str) -> isize {
+    signal_strength(&Day10.parse(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> String {
+    render_crt(&Day10.parse(input).unwrap())
+}
+
+fn signal_strength(program: &[Instruction]) -> isize {
+    let mut cpu = Cpu::new();
+    cpu.process_program(program);
+    cpu.x_history
+        .iter()
+        .skip(18)
+        .step_by(40)
+        .enumerate()
+        .map(|(i, x)| (i as isize * 40 + 20) * x)
+        .sum()
+}
+
+fn render_crt(program: &[Instruction]) -> String {
+    let mut cpu = Cpu::new();
+    cpu.process_program(program);
+
+    cpu.image
+        .chunks(40)
+        .map(|slice| slice.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 13140);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let expected_res = include_str!("../data/part2_sample.txt").trim().to_string();
+    let res = problem2(input);
+    assert_eq!(res, expected_res);
+}
@@ -0,0 +1,278 @@
+extern crate num;
+use common::{parse_number, Answer, ParseError, Solution};
+use num::Integer;
+use std::{cell::RefCell, str::FromStr};
+
+#[derive(Clone, Copy)]
+pub enum Operation {
+    Add(Option<u64>),
+    Multiply(Option<u64>),
+}
+
+enum Modifer {
+    Worried(u64),
+    NotWorried(u64),
+}
+
+impl Modifer {
+    fn apply(&self, value: u64) -> u64 {
+        match self {
+            Self::Worried(n) => value / n,
+            Self::NotWorried(n) => value % n,
+        }
+    }
+}
+
+impl Operation {
+    fn apply(&self, value: u64) -> u64 {
+        match self {
+            Operation::Add(Some(n)) => value + n,
+            Operation::Add(None) => value + value,
+            Operation::Multiply(Some(n)) => value * n,
+            Operation::Multiply(None) => value * value,
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = field(s, "Operation: new = old ")?;
+        let (operator, operand) = expression
+            .split_once(' ')
+            .ok_or_else(|| ParseError::missing(s, "an operator and an operand"))?;
+        let num = match operand {
+            "old" => None,
+            n => Some(parse_number(s, n)?),
+        };
+
+        match operator {
+            "+" => Ok(Self::Add(num)),
+            "*" => Ok(Self::Multiply(num)),
+            o => Err(ParseError::new(s, o, "`+` or `*`")),
+        }
+    }
+}
+
+/// What the notes say about one monkey, before the monkeys are linked together.
+pub struct MonkeyNotes {
+    items: Vec<u64>,
+    operation: Operation,
+    test: u64,
+    true_monkey: usize,
+    false_monkey: usize,
+}
+
+/// The rest of `line` after `prefix` (ignoring indentation).
+fn field<'a>(line: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    let trimmed = line.trim_start();
+    trimmed
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(line, trimmed, format!("`{}`", prefix.trim_end())))
+}
+
+fn next_line<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    section: &str,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    lines
+        .next()
+        .ok_or_else(|| ParseError::missing(section, expected))
+}
+
+/// Parses one monkey's section of the notes. Errors are relative to the section.
+fn parse_monkey(section: &str, num_monkeys: usize) -> Result<MonkeyNotes, ParseError> {
+    let mut lines = section.lines();
+    let line = next_line(&mut lines, section, "`Monkey <number>:`")?;
+    field(line, "Monkey ").map_err(|e| e.within(section, line))?;
+
+    let line = next_line(&mut lines, section, "the starting items")?;
+    let items = field(line, "Starting items: ")
+        .and_then(|items| {
+            items
+                .split(", ")
+                .map(|item| parse_number(line, item))
+                .collect()
+        })
+        .map_err(|e| e.within(section, line))?;
+
+    let line = next_line(&mut lines, section, "the operation")?;
+    let operation = line
+        .parse::<Operation>()
+        .map_err(|e| e.within(section, line))?;
+
+    let line = next_line(&mut lines, section, "the test")?;
+    let test = field(line, "Test: divisible by ")
+        .and_then(|n| parse_number(line, n))
+        .map_err(|e| e.within(section, line))?;
+
+    let mut target = |prefix: &str| {
+        let line = next_line(&mut lines, section, &format!("`{}`", prefix.trim_end()))?;
+        field(line, prefix)
+            .and_then(|n| match parse_number(line, n)? {
+                monkey if monkey < num_monkeys => Ok(monkey),
+                _ => Err(ParseError::new(
+                    line,
+                    n,
+                    format!("a monkey below {}", num_monkeys),
+                )),
+            })
+            .map_err(|e| e.within(section, line))
+    };
+    let true_monkey = target("If true: throw to monkey ")?;
+    let false_monkey = target("If false: throw to monkey ")?;
+
+    Ok(MonkeyNotes {
+        items,
+        operation,
+        test,
+        true_monkey,
+        false_monkey,
+    })
+}
+
+fn parse_notes(input: &str) -> Result<Vec<MonkeyNotes>, ParseError> {
+    let sections: Vec<&str> = input.split("\n\n").collect();
+    sections
+        .iter()
+        .map(|section| parse_monkey(section, sections.len()).map_err(|e| e.within(input, section)))
+        .collect()
+}
+
+#[derive(Default)]
+struct Monkey<'a> {
+    items: Vec<u64>,
+    operation: Option<Operation>,
+    test: u64,
+    true_monkey: Option<&'a RefCell<Monkey<'a>>>,
+    false_monkey: Option<&'a RefCell<Monkey<'a>>>,
+    num_inspections: usize,
+}
+
+impl<'a> Monkey<'a> {
+    fn update(
+        &mut self,
+        items: Vec<u64>,
+        operation: Operation,
+        test: u64,
+        true_monkey: &'a RefCell<Monkey<'a>>,
+        false_monkey: &'a RefCell<Monkey<'a>>,
+    ) {
+        self.items = items;
+        self.operation = Some(operation);
+        self.test = test;
+        self.true_monkey = Some(true_monkey);
+        self.false_monkey = Some(false_monkey);
+    }
+    fn receive_item(&mut self, item: u64) {
+        self.items.push(item)
+    }
+    fn throw_items(&mut self, modifier: &Modifer) {
+        let operation = self.operation.as_ref().unwrap();
+        let mut true_monkey = self.true_monkey.as_ref().unwrap().borrow_mut();
+        let mut false_monkey = self.false_monkey.as_ref().unwrap().borrow_mut();
+
+        for item in self.items.iter() {
+            let new_item = modifier.apply(operation.apply(*item));
+            if new_item.is_multiple_of(self.test) {
+                true_monkey.receive_item(new_item)
+            } else {
+                false_monkey.receive_item(new_item)
+            };
+        }
+        self.num_inspections += self.items.len();
+        self.items.clear();
+    }
+}
+
+fn solve_problem(notes: &[MonkeyNotes], rounds: u64, worried: bool) -> u64 {
+    let monkeys: Vec<RefCell<Monkey>> = (0..notes.len())
+        .map(|_| RefCell::new(Monkey::default()))
+        .collect();
+
+    for (monkey, notes) in monkeys.iter().zip(notes) {
+        monkey.borrow_mut().update(
+            notes.items.clone(),
+            notes.operation,
+            notes.test,
+            &monkeys[notes.true_monkey],
+            &monkeys[notes.false_monkey],
+        );
+    }
+
+    let modifier = if worried {
+        Modifer::Worried(3)
+    } else {
+        let lcm = monkeys
+            .iter()
+            .map(|m| m.borrow().test)
+            .reduce(|lcm, n| lcm.lcm(&n))
+            .unwrap();
+        Modifer::NotWorried(lcm)
+    };
+
+    for _round in 0..rounds {
+        for monkey in monkeys.iter() {
+            monkey.borrow_mut().throw_items(&modifier);
+        }
+    }
+
+    let mut monkey_inspections: Vec<usize> = monkeys
+        .iter()
+        .map(|monkey| monkey.borrow().num_inspections)
+        .collect();
+
+    monkey_inspections.sort();
+
+    monkey_inspections
+        .into_iter()
+        .rev()
+        .take(2)
+        .reduce(|v1, v2| v1 * v2)
+        .unwrap() as u64
+}
+
+#[derive(Default)]
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<MonkeyNotes>;
+
+    fn parse(&self, input: &str) -> Result<Vec<MonkeyNotes>, ParseError> {
+        parse_notes(input)
+    }
+
+    fn problem1(&self, notes: &Vec<MonkeyNotes>) -> Answer {
+        solve_problem(notes, 20, true).into()
+    }
+
+    fn problem2(&self, notes: &Vec<MonkeyNotes>) -> Answer {
+        solve_problem(notes, 10000, false).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u64 {
+    solve_problem(&parse_notes(input).unwrap(), 20, true)
+}
+
+pub fn problem2(input: &str) -> u64 {
+    solve_problem(&parse_notes(input).unwrap(), 10000, false)
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 10605);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 2713310158);
+}
@@ -0,0 +1,151 @@
+use common::{
+    grid::Position,
+    search::{bfs, Path},
+    Answer, Direction4, Explanation, Grid, ParseError, Solution,
+};
+
+const S: u8 = b'S';
+const E: u8 = b'E';
+
+fn transform_height(height: u8) -> u8 {
+    if height == E {
+        b'z'
+    } else if height == S {
+        b'a'
+    } else {
+        height
+    }
+}
+
+/// Shortest route from any of the `starts` up to `E`, climbing at most one step at a time.
+fn shortest_path(starts: impl IntoIterator<Item = Position>, grid: &Grid<u8>) -> Path<Position> {
+    bfs(
+        starts,
+        |&position| {
+            let max_height = transform_height(grid[position]) + 1;
+            grid.neighbors4(position)
+                .filter(move |&next| transform_height(grid[next]) <= max_height)
+        },
+        |&position| grid[position] == E,
+    )
+    .expect("Could not find target E")
+}
+
+fn parse_heightmap(input: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::parse(input, "a height, `S` or `E`", |c| {
+        (c.is_ascii_lowercase() || c == S as char || c == E as char).then_some(c as u8)
+    })?;
+    for (marker, expected) in [(S, "the start `S`"), (E, "the target `E`")] {
+        if grid.position(|&b| b == marker).is_none() {
+            return Err(ParseError::missing(input, expected));
+        }
+    }
+    Ok(grid)
+}
+
+fn path_from_start(grid: &Grid<u8>) -> Path<Position> {
+    let start = grid.position(|&b| b == S).expect("parsing checks for S");
+    shortest_path([start], grid)
+}
+
+fn path_from_any_a(grid: &Grid<u8>) -> Path<Position> {
+    let starts = grid
+        .positions()
+        .filter(|&position| transform_height(grid[position]) == b'a');
+    shortest_path(starts, grid)
+}
+
+fn fewest_steps(grid: &Grid<u8>) -> u32 {
+    path_from_start(grid).cost as u32
+}
+
+fn fewest_steps_from_any_a(grid: &Grid<u8>) -> u32 {
+    path_from_any_a(grid).cost as u32
+}
+
+/// Draws the route over the heightmap the way the puzzle does, with an arrow on every square
+/// pointing to the next one and `.` on squares the route doesn't cross.
+pub fn render_route(grid: &Grid<u8>, route: &[Position]) -> String {
+    let mut picture = grid.map(|_| '.');
+    for step in route.windows(2) {
+        let direction = Direction4::ALL
+            .into_iter()
+            .find(|&d| grid.step(step[0], d) == Some(step[1]))
+            .expect("routes only take single steps");
+        picture[step[0]] = match direction {
+            Direction4::Up => '^',
+            Direction4::Right => '>',
+            Direction4::Down => 'v',
+            Direction4::Left => '<',
+        };
+    }
+    if let Some(&end) = route.last() {
+        picture[end] = 'E';
+    }
+    picture.to_string()
+}
+
+#[derive(Default)]
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Grid<u8>;
+
+    fn parse(&self, input: &str) -> Result<Grid<u8>, ParseError> {
+        parse_heightmap(input)
+    }
+
+    fn problem1(&self, grid: &Grid<u8>) -> Answer {
+        fewest_steps(grid).into()
+    }
+
+    fn problem2(&self, grid: &Grid<u8>) -> Answer {
+        fewest_steps_from_any_a(grid).into()
+    }
+
+    /// The route the part found, drawn over the heightmap.
+    fn explain(&self, part: u8, grid: &Grid<u8>) -> Option<Explanation> {
+        let path = match part {
+            1 => path_from_start(grid),
+            _ => path_from_any_a(grid),
+        };
+        Some(Explanation {
+            text: render_route(grid, &path.states),
+            json: None,
+        })
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    fewest_steps(&parse_heightmap(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u32 {
+    fewest_steps_from_any_a(&parse_heightmap(input).unwrap())
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 31);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 29);
+}
+
+#[test]
+fn test_route() {
+    let grid = parse_heightmap(include_str!("../data/sample.txt")).unwrap();
+    let route = Day12.explain(1, &grid).unwrap().text;
+    assert_eq!(route.lines().count(), 5);
+    assert_eq!(route.chars().filter(|c| "^>v<".contains(*c)).count(), 31);
+    assert!(route.starts_with('v') || route.starts_with('>'));
+    assert_eq!(route.lines().nth(2).unwrap().chars().nth(5), Some('E'));
+}
@@ -0,0 +1,221 @@
+use common::{parse_number, Answer, ParseError, Solution};
+use std::str::FromStr;
+
+#[derive(Clone, Debug)]
+pub enum Packet {
+    Num(u32),
+    Nested(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self.is_right_order_helper(other) {
+            Some(true) => std::cmp::Ordering::Less,
+            Some(false) => std::cmp::Ordering::Greater,
+            None => std::cmp::Ordering::Equal,
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl Packet {
+    /// Parses the packet at the start of `rest` (a slice of `line`), returning it and whatever
+    /// follows it.
+    fn parse_packet<'a>(line: &str, rest: &'a str) -> Result<(Self, &'a str), ParseError> {
+        let Some(mut rest) = rest.strip_prefix('[') else {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            if end == 0 {
+                return Err(ParseError::new(line, first_char(rest), "a number or `[`"));
+            }
+            return Ok((Packet::Num(parse_number(line, &rest[..end])?), &rest[end..]));
+        };
+
+        let mut v: Vec<Self> = Vec::new();
+        if let Some(rest) = rest.strip_prefix(']') {
+            return Ok((Self::Nested(v), rest));
+        }
+        loop {
+            let (packet, after) = Self::parse_packet(line, rest)?;
+            v.push(packet);
+            match after.chars().next() {
+                Some(',') => rest = &after[1..],
+                Some(']') => return Ok((Self::Nested(v), &after[1..])),
+                _ => return Err(ParseError::new(line, first_char(after), "`,` or `]`")),
+            }
+        }
+    }
+
+    fn divider(n: u32) -> Self {
+        Self::Nested(vec![Self::Nested(vec![Self::Num(n)])])
+    }
+
+    fn len(&self) -> usize {
+        match &self {
+            Self::Num(_) => 1,
+            Self::Nested(v) => v.len(),
+        }
+    }
+
+    fn is_right_order(&self, other: &Packet) -> bool {
+        self.is_right_order_helper(other).unwrap_or(true)
+    }
+
+    fn is_right_order_helper(&self, other: &Packet) -> Option<bool> {
+        match (&self, &other) {
+            (Self::Num(p1), Self::Num(p2)) => {
+                if p1 == p2 {
+                    None
+                } else {
+                    Some(p1 < p2)
+                }
+            }
+
+            (Self::Nested(v1), Self::Nested(v2)) => {
+                match v1
+                    .iter()
+                    .zip(v2.iter())
+                    .find(|(p1, p2)| p1.is_right_order_helper(p2).is_some())
+                {
+                    Some((p1, p2)) => p1.is_right_order_helper(p2),
+                    None => {
+                        if self.len() == other.len() {
+                            None
+                        } else {
+                            Some(self.len() < other.len())
+                        }
+                    }
+                }
+            }
+            (Self::Num(p), _) => Self::Nested(vec![Packet::Num(*p)]).is_right_order_helper(other),
+            (_, Self::Num(p)) => self.is_right_order_helper(&Self::Nested(vec![Packet::Num(*p)])),
+        }
+    }
+}
+
+/// The first character of `s`, or an empty slice at its end.
+fn first_char(s: &str) -> &str {
+    &s[..s.chars().next().map_or(0, char::len_utf8)]
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::parse_packet(s, s)? {
+            (packet, "") => Ok(packet),
+            (_, rest) => Err(ParseError::new(
+                s,
+                first_char(rest),
+                "the end of the packet",
+            )),
+        }
+    }
+}
+
+/// Parses the pairs of packets, which are separated by blank lines.
+fn parse_pairs(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+    let mut packets = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse().map_err(|e: ParseError| e.within(input, line)));
+
+    let mut pairs = Vec::new();
+    while let Some(left) = packets.next() {
+        let right = packets
+            .next()
+            .unwrap_or_else(|| Err(ParseError::missing(input, "a second packet")));
+        pairs.push((left?, right?));
+    }
+    Ok(pairs)
+}
+
+#[derive(Default)]
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<(Packet, Packet)>;
+
+    fn parse(&self, input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+        parse_pairs(input)
+    }
+
+    fn problem1(&self, pairs: &Vec<(Packet, Packet)>) -> Answer {
+        sum_ordered_indices(pairs).into()
+    }
+
+    fn problem2(&self, pairs: &Vec<(Packet, Packet)>) -> Answer {
+        decoder_key(pairs).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    sum_ordered_indices(&parse_pairs(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u32 {
+    decoder_key(&parse_pairs(input).unwrap())
+}
+
+fn sum_ordered_indices(pairs: &[(Packet, Packet)]) -> u32 {
+    pairs
+        .iter()
+        .enumerate()
+        .map(|(i, (p1, p2))| {
+            if p1.is_right_order(p2) {
+                // println!("{} is in the right order", i + 1);
+                (i + 1) as u32
+            } else {
+                // println!("{} is NOT in the right order", i + 1);
+                0_u32
+            }
+        })
+        .sum()
+    // 224 is too low
+}
+
+fn decoder_key(pairs: &[(Packet, Packet)]) -> u32 {
+    let mut packets: Vec<Packet> = pairs
+        .iter()
+        .flat_map(|(p1, p2)| vec![p1.clone(), p2.clone()])
+        .collect();
+    let divider2 = Packet::divider(2);
+    let divider6 = Packet::divider(6);
+    packets.push(divider2.clone());
+    packets.push(divider6.clone());
+    packets.sort();
+
+    ((packets.iter().position(|p| p == &divider2).unwrap() + 1)
+        * (packets.iter().position(|p| p == &divider6).unwrap() + 1)) as u32
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 13);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 140);
+}
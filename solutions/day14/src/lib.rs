@@ -0,0 +1,201 @@
+use common::{grid::Position, parse_lines, Answer, Grid, ParseError, Point2, Solution};
+use std::fmt::Display;
+
+pub type Coordinate = Point2<usize>;
+
+#[derive(Clone)]
+enum Block {
+    Air,
+    Rock,
+    Sand,
+    Generator,
+}
+
+impl Display for Block {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let block = match &self {
+            Block::Air => ".",
+            Block::Rock => "#",
+            Block::Sand => "o",
+            Block::Generator => "+",
+        };
+        write!(f, "{}", block)
+    }
+}
+
+struct Cave {
+    sand_generator: Position,
+    grid: Grid<Block>,
+}
+
+impl Cave {
+    fn new(
+        rocks: Vec<Coordinate>,
+        sand_generator: Coordinate,
+        num_rows: usize,
+        num_cols: usize,
+    ) -> Self {
+        let mut grid = Grid::new(num_cols, num_rows, Block::Air);
+        for rock_coordinate in rocks {
+            grid[rock_coordinate] = Block::Rock;
+        }
+        grid[sand_generator] = Block::Generator;
+        Self {
+            sand_generator,
+            grid,
+        }
+    }
+
+    fn drop_sand_block(&mut self) -> bool {
+        let Point2 { mut x, mut y } = self.sand_generator;
+
+        while y < self.grid.height() - 1 && x > 0 && x < self.grid.width() - 1 {
+            y += 1;
+            let down_block = &self.grid[Point2::new(x, y)];
+            let left_block = &self.grid[Point2::new(x - 1, y)];
+            let right_block = &self.grid[Point2::new(x + 1, y)];
+
+            match (left_block, down_block, right_block) {
+                (_, Block::Air, _) => continue,
+                (Block::Air, _, _) => {
+                    x -= 1;
+                    continue;
+                }
+                (_, _, Block::Air) => {
+                    x += 1;
+                    continue;
+                }
+                (_, _, _) => {
+                    let rest = Point2::new(x, y - 1);
+                    self.grid[rest] = Block::Sand;
+                    return rest != self.sand_generator;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.grid)
+    }
+}
+
+/// Parses the paths of rock, one per line.
+fn parse_paths(input: &str) -> Result<Vec<Vec<Coordinate>>, ParseError> {
+    parse_lines(input, |line| {
+        line.split(" -> ")
+            .map(|coord| coord.parse().map_err(|e: ParseError| e.within(line, coord)))
+            .collect()
+    })
+}
+
+/// The points on the horizontal or vertical line from `a` to `b`, shifted left by `dx`.
+fn gen_coordinates(a: &Coordinate, b: &Coordinate, dx: usize) -> Vec<Coordinate> {
+    // vertical line
+    if a.x == b.x {
+        let min = a.y.min(b.y);
+        let max = a.y.max(b.y);
+        (min..=max).map(|y| Point2::new(a.x - dx, y)).collect()
+    } else {
+        let min = a.x.min(b.x);
+        let max = a.x.max(b.x);
+        (min..=max).map(|x| Point2::new(x - dx, a.y)).collect()
+    }
+}
+
+fn drop_sand(mut cave: Cave) -> u32 {
+    for ans in 0.. {
+        if !cave.drop_sand_block() {
+            return ans;
+        }
+    }
+    unreachable!()
+}
+
+fn create_cave(coordinates: &[Vec<Coordinate>], add_infinite_bottom: bool) -> Cave {
+    // normalize the coordinates, and then use window to create lines
+    let (min_x, max_x, max_y) = if add_infinite_bottom {
+        let max_y = coordinates.iter().flatten().map(|c| c.y).max().unwrap() + 2;
+        let min_x = 0;
+        // create "infinite" horizontal line
+        let max_x = coordinates.iter().flatten().map(|c| c.x).max().unwrap() + max_y;
+        (min_x, max_x, max_y)
+    } else {
+        let min_x = coordinates.iter().flatten().map(|c| c.x).min().unwrap();
+        let max_x = coordinates.iter().flatten().map(|c| c.x).max().unwrap();
+        let max_y = coordinates.iter().flatten().map(|c| c.y).max().unwrap();
+        (min_x, max_x, max_y)
+    };
+
+    let sand_generator_coordinate = Point2::new(500 - min_x, 0);
+    let mut rock_coordinates: Vec<Coordinate> = coordinates
+        .iter()
+        .flat_map(|v| {
+            v.windows(2)
+                .flat_map(|w| gen_coordinates(&w[0], &w[1], min_x))
+                .collect::<Vec<Coordinate>>()
+        })
+        .collect();
+
+    if add_infinite_bottom {
+        rock_coordinates.append(&mut gen_coordinates(
+            &Point2::new(min_x, max_y),
+            &Point2::new(max_x, max_y),
+            min_x,
+        ));
+    }
+
+    Cave::new(
+        rock_coordinates,
+        sand_generator_coordinate,
+        max_y + 1,
+        max_x - min_x + 1,
+    )
+}
+
+#[derive(Default)]
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Vec<Vec<Coordinate>>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<Coordinate>>, ParseError> {
+        parse_paths(input)
+    }
+
+    fn problem1(&self, paths: &Vec<Vec<Coordinate>>) -> Answer {
+        drop_sand(create_cave(paths, false)).into()
+    }
+
+    fn problem2(&self, paths: &Vec<Vec<Coordinate>>) -> Answer {
+        (drop_sand(create_cave(paths, true)) + 1).into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    let cave = create_cave(&parse_paths(input).unwrap(), false);
+    drop_sand(cave)
+}
+
+pub fn problem2(input: &str) -> u32 {
+    let cave = create_cave(&parse_paths(input).unwrap(), true);
+    drop_sand(cave) + 1
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 24);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 93);
+}
@@ -1,43 +1,48 @@
-use std::str::FromStr;
+use std::{ops::RangeInclusive, str::FromStr};
+
+use common::{parse_number, ParseError, Point2};
 pub const MAX_XY: isize = 4000000;
 
 #[derive(Debug)]
 pub struct Sensor {
     pub position: Coordinate,
     pub beacon: Coordinate,
-    pub distance_to_beacon: usize,
+    pub distance_to_beacon: isize,
+}
+
+/// Parses a token like `x=-2,` into the number between `prefix` and `suffix`.
+fn coordinate_value(
+    line: &str,
+    token: Option<&str>,
+    prefix: &str,
+    suffix: &str,
+) -> Result<isize, ParseError> {
+    let expected = || format!("`{}<number>{}`", prefix, suffix);
+    let token = token.ok_or_else(|| ParseError::missing(line, expected()))?;
+    let value = token
+        .strip_prefix(prefix)
+        .and_then(|t| t.strip_suffix(suffix))
+        .ok_or_else(|| ParseError::new(line, token, expected()))?;
+    parse_number(line, value)
 }
 
 impl FromStr for Sensor {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // skip sensor,at
         let mut split_str = s.split_whitespace().skip(2);
-        let x_str = split_str.next().unwrap();
-        // exclude x= and ,
-        let x = x_str[2..x_str.len() - 1].parse::<isize>().unwrap();
-
-        let y_str = split_str.next().unwrap();
-        // exclude y= and :
-        let y = y_str[2..y_str.len() - 1].parse::<isize>().unwrap();
-
-        let position = Coordinate { x, y };
+        let x = coordinate_value(s, split_str.next(), "x=", ",")?;
+        let y = coordinate_value(s, split_str.next(), "y=", ":")?;
+        let position = Point2::new(x, y);
 
         // skip closest,beacon,is,at
         let mut split_str = split_str.skip(4);
+        let x = coordinate_value(s, split_str.next(), "x=", ",")?;
+        let y = coordinate_value(s, split_str.next(), "y=", "")?;
+        let beacon = Point2::new(x, y);
 
-        let x_str = split_str.next().unwrap();
-        // exclude x= and ,
-        let x = x_str[2..x_str.len() - 1].parse::<isize>().unwrap();
-
-        let y_str = split_str.next().unwrap();
-        // exclude y=
-        let y = y_str[2..].parse::<isize>().unwrap();
-
-        let beacon = Coordinate { x, y };
-
-        let distance_to_beacon = position.manhattan_distance(&beacon);
+        let distance_to_beacon = position.manhattan(beacon);
 
         Ok(Self {
             position,
@@ -47,18 +52,33 @@ impl FromStr for Sensor {
     }
 }
 
-#[derive(Hash, Debug, PartialEq, Eq)]
-pub struct Coordinate {
-    pub x: isize,
-    pub y: isize,
-}
+impl Sensor {
+    /// Whether `coordinate` is as close to the sensor as the beacon it found, the beacon itself
+    /// included.
+    pub fn covers(&self, coordinate: Coordinate) -> bool {
+        self.position.manhattan(coordinate) <= self.distance_to_beacon
+    }
 
-impl Coordinate {
-    pub fn manhattan_distance(&self, other: &Self) -> usize {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    /// The x coordinates the sensor can see on row `y`, if it reaches that far.
+    pub fn coverage(&self, y: isize) -> Option<RangeInclusive<isize>> {
+        let reach = self.distance_to_beacon - self.position.y.abs_diff(y) as isize;
+        (reach >= 0).then(|| self.position.x - reach..=self.position.x + reach)
     }
 
-    pub fn tuning_distance(&self) -> usize {
-        (self.x * MAX_XY + self.y) as usize
+    /// The lines just out of the sensor's reach, two with `x + y` the same all along them and
+    /// two with `x - y` the same, as those sums and differences.
+    pub fn boundary(&self) -> ([isize; 2], [isize; 2]) {
+        let Point2 { x, y } = self.position;
+        let reach = self.distance_to_beacon + 1;
+        (
+            [x + y - reach, x + y + reach],
+            [x - y - reach, x - y + reach],
+        )
     }
 }
+
+pub type Coordinate = Point2<isize>;
+
+pub fn tuning_frequency(coordinate: Coordinate) -> usize {
+    (coordinate.x * MAX_XY + coordinate.y) as usize
+}
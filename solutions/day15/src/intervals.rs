@@ -0,0 +1,82 @@
+use std::ops::RangeInclusive;
+
+/// A set of integers kept as sorted, disjoint ranges, merging any that overlap or touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Intervals(Vec<RangeInclusive<isize>>);
+
+impl FromIterator<RangeInclusive<isize>> for Intervals {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<isize>>>(iter: I) -> Self {
+        let mut ranges: Vec<_> = iter.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_unstable_by_key(|r| *r.start());
+        let mut merged: Vec<RangeInclusive<isize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if *range.start() <= last.end() + 1 => {
+                    if range.end() > last.end() {
+                        *last = *last.start()..=*range.end();
+                    }
+                }
+                _ => merged.push(range),
+            }
+        }
+        Self(merged)
+    }
+}
+
+impl Intervals {
+    pub fn ranges(&self) -> &[RangeInclusive<isize>] {
+        &self.0
+    }
+
+    /// How many integers are in the set.
+    pub fn len(&self) -> usize {
+        self.0
+            .iter()
+            .map(|r| (r.end() - r.start() + 1) as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, value: isize) -> bool {
+        let i = self.0.partition_point(|r| *r.end() < value);
+        self.0.get(i).is_some_and(|r| r.contains(&value))
+    }
+
+    /// The smallest integer of `within` that isn't in the set.
+    pub fn first_gap(&self, within: RangeInclusive<isize>) -> Option<isize> {
+        let mut candidate = *within.start();
+        for range in &self.0 {
+            if *range.start() > candidate {
+                break;
+            }
+            candidate = candidate.max(range.end() + 1);
+        }
+        (candidate <= *within.end()).then_some(candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intervals() {
+        let intervals: Intervals = [12..=12, 2..=2, -2..=2, 16..=24, 2..=14]
+            .into_iter()
+            .collect();
+        assert_eq!(intervals.ranges(), [-2..=14, 16..=24]);
+        assert_eq!(intervals.len(), 26);
+        assert!(intervals.contains(-2) && intervals.contains(20));
+        assert!(!intervals.contains(15) && !intervals.contains(25));
+        assert_eq!(intervals.first_gap(0..=20), Some(15));
+        assert_eq!(intervals.first_gap(16..=24), None);
+        assert_eq!(intervals.first_gap(-5..=0), Some(-5));
+
+        // touching ranges merge too
+        let intervals: Intervals = [0..=3, 4..=6].into_iter().collect();
+        assert_eq!(intervals.ranges(), [0..=6]);
+    }
+}
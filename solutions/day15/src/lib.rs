@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use common::{parse_lines, Answer, ParseError, Point2, Solution};
+use entities::{tuning_frequency, Coordinate, Sensor, MAX_XY};
+use intervals::Intervals;
+
+pub mod entities;
+pub mod intervals;
+#[cfg(test)]
+mod oracle;
+
+pub struct Day15 {
+    /// Row to count the positions where a beacon cannot be present in
+    row: isize,
+    /// Largest x and y coordinate the distress beacon can have
+    max_xy: isize,
+}
+
+impl Default for Day15 {
+    fn default() -> Self {
+        Self {
+            row: 2_000_000,
+            max_xy: MAX_XY,
+        }
+    }
+}
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<Sensor>;
+
+    fn sample() -> Self {
+        Self {
+            row: 10,
+            max_xy: 20,
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Sensor>, ParseError> {
+        get_sensors(input)
+    }
+
+    fn problem1(&self, sensors: &Vec<Sensor>) -> Answer {
+        count_impossible(sensors, self.row).into()
+    }
+
+    fn problem2(&self, sensors: &Vec<Sensor>) -> Answer {
+        find_tuning_frequency(sensors, self.max_xy).into()
+    }
+}
+
+fn get_sensors(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    parse_lines(input, str::parse)
+}
+
+pub fn problem1(input: &str, y: isize) -> usize {
+    count_impossible(&get_sensors(input).unwrap(), y)
+}
+
+pub fn problem2(input: &str, max_xy: isize) -> usize {
+    find_tuning_frequency(&get_sensors(input).unwrap(), max_xy)
+}
+
+/// How many positions on row `y` can't have a beacon: those some sensor sees, except for the
+/// beacons already there.
+fn count_impossible(sensors: &[Sensor], y: isize) -> usize {
+    let covered: Intervals = sensors.iter().filter_map(|s| s.coverage(y)).collect();
+    let beacons: HashSet<isize> = sensors
+        .iter()
+        .filter(|s| s.beacon.y == y)
+        .map(|s| s.beacon.x)
+        .collect();
+    covered.len() - beacons.len()
+}
+
+fn in_area(coordinate: Coordinate, max_xy: isize) -> bool {
+    (0..=max_xy).contains(&coordinate.x) && (0..=max_xy).contains(&coordinate.y)
+}
+
+/// Where boundaries just out of reach of the sensors cross, the likely spots for the distress
+/// beacon: with a single position left, every side of it has to be seen by some sensor.
+fn crossings(sensors: &[Sensor]) -> impl Iterator<Item = Coordinate> {
+    let (mut sums, mut differences): (Vec<isize>, Vec<isize>) = (Vec::new(), Vec::new());
+    for sensor in sensors {
+        let (sum, difference) = sensor.boundary();
+        sums.extend(sum);
+        differences.extend(difference);
+    }
+    sums.sort_unstable();
+    sums.dedup();
+    differences.sort_unstable();
+    differences.dedup();
+    sums.into_iter().flat_map(move |sum| {
+        differences
+            .iter()
+            .filter(move |&&difference| (sum + difference) % 2 == 0)
+            .map(move |&difference| Point2::new((sum + difference) / 2, (sum - difference) / 2))
+            .collect::<Vec<_>>()
+    })
+}
+
+/// The first position, row by row, that no sensor sees.
+fn scan_rows(sensors: &[Sensor], max_xy: isize) -> Option<Coordinate> {
+    (0..=max_xy).find_map(|y| {
+        let covered: Intervals = sensors.iter().filter_map(|s| s.coverage(y)).collect();
+        covered.first_gap(0..=max_xy).map(|x| Point2::new(x, y))
+    })
+}
+
+/// The one position in the area no sensor sees. It's nearly always where the boundaries of
+/// sensors cross, so those are checked before scanning the rows for the rest, like a corner of
+/// the area.
+fn find_distress_beacon(sensors: &[Sensor], max_xy: isize) -> Option<Coordinate> {
+    crossings(sensors)
+        .filter(|&coordinate| in_area(coordinate, max_xy))
+        .find(|&coordinate| !sensors.iter().any(|s| s.covers(coordinate)))
+        .or_else(|| scan_rows(sensors, max_xy))
+}
+
+fn find_tuning_frequency(sensors: &[Sensor], max_xy: isize) -> usize {
+    let beacon = find_distress_beacon(sensors, max_xy).expect("one position is left");
+    tuning_frequency(beacon)
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input, 10);
+    assert_eq!(res, 26);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input, 20);
+    assert_eq!(res, 56000011);
+}
+
+#[test]
+fn test_coverage() {
+    let sensors = get_sensors(include_str!("../data/sample.txt")).unwrap();
+    // the sensor at 8,7 in the puzzle's picture
+    let sensor = &sensors[6];
+    assert_eq!(sensor.coverage(7), Some(-1..=17));
+    assert_eq!(sensor.coverage(16), Some(8..=8));
+    assert_eq!(sensor.coverage(17), None);
+
+    let beacon = Point2::new(14, 11);
+    assert_eq!(crossings(&sensors).find(|&c| c == beacon), Some(beacon));
+    assert_eq!(scan_rows(&sensors, 20), Some(beacon));
+}
@@ -0,0 +1,119 @@
+//! Brute-force answers for small areas, checked against the solver on random sensor layouts
+//! that leave exactly one position for the distress beacon.
+
+use common::Point2;
+use proptest::prelude::*;
+
+use crate::{
+    count_impossible,
+    entities::{Coordinate, Sensor},
+    find_distress_beacon,
+};
+
+fn sensor(position: Coordinate, beacon: Coordinate) -> Sensor {
+    Sensor {
+        position,
+        beacon,
+        distance_to_beacon: position.manhattan(beacon),
+    }
+}
+
+/// Counts row `y` one position at a time, as far out as any sensor reaches.
+fn brute_force_count(sensors: &[Sensor], y: isize) -> usize {
+    let reach = sensors
+        .iter()
+        .map(|s| s.distance_to_beacon)
+        .max()
+        .unwrap_or(0);
+    let min_x = sensors.iter().map(|s| s.position.x).min().unwrap_or(0) - reach;
+    let max_x = sensors.iter().map(|s| s.position.x).max().unwrap_or(0) + reach;
+    (min_x..=max_x)
+        .map(|x| Point2::new(x, y))
+        .filter(|&c| sensors.iter().all(|s| s.beacon != c) && sensors.iter().any(|s| s.covers(c)))
+        .count()
+}
+
+/// Every position in the area that no sensor sees.
+fn brute_force_gaps(sensors: &[Sensor], max_xy: isize) -> Vec<Coordinate> {
+    (0..=max_xy)
+        .flat_map(|y| (0..=max_xy).map(move |x| Point2::new(x, y)))
+        .filter(|&c| !sensors.iter().any(|s| s.covers(c)))
+        .collect()
+}
+
+/// SplitMix64, enough to make random choices from a seed proptest picks.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..=high`.
+    fn between(&mut self, low: isize, high: isize) -> isize {
+        low + (self.next() % (high - low + 1) as u64) as isize
+    }
+}
+
+/// Sensors that see every position in the area but `gap`, added one at a time for a position
+/// none of them sees yet. Each one reaches somewhere short of the gap, with its beacon at a
+/// random spot that far away.
+fn layout(max_xy: isize, gap: Coordinate, seed: u64) -> Vec<Sensor> {
+    let mut random = Random(seed);
+    let mut sensors: Vec<Sensor> = Vec::new();
+    loop {
+        let uncovered: Vec<Coordinate> = brute_force_gaps(&sensors, max_xy)
+            .into_iter()
+            .filter(|&c| c != gap)
+            .collect();
+        if uncovered.is_empty() {
+            return sensors;
+        }
+        let target = uncovered[random.next() as usize % uncovered.len()];
+
+        // somewhere near the target that's closer to it than to the gap, if that's quick to
+        // find, or else on the far side of the target from the gap
+        let position = (0..10)
+            .map(|_| target + Point2::new(random.between(-3, 3), random.between(-3, 3)))
+            .find(|&p| p.manhattan(target) < p.manhattan(gap) && p.manhattan(gap) >= 2)
+            .unwrap_or_else(|| {
+                let away = Point2::new((target.x - gap.x).signum(), (target.y - gap.y).signum());
+                target + away
+            });
+        let reach = random.between(
+            position.manhattan(target).max(1),
+            position.manhattan(gap) - 1,
+        );
+        let dx = random.between(-reach, reach);
+        let dy = (reach - dx.abs()) * if random.next().is_multiple_of(2) { 1 } else { -1 };
+        sensors.push(sensor(position, position + Point2::new(dx, dy)));
+    }
+}
+
+#[test]
+fn test_oracle() {
+    let sensors = crate::get_sensors(include_str!("../data/sample.txt")).unwrap();
+    assert_eq!(brute_force_count(&sensors, 10), 26);
+    assert_eq!(brute_force_gaps(&sensors, 20), [Point2::new(14, 11)]);
+}
+
+proptest! {
+    #[test]
+    fn solver_matches_oracle(
+        (max_xy, gap) in (1..30_isize).prop_flat_map(|max_xy| {
+            (Just(max_xy), (0..=max_xy, 0..=max_xy).prop_map(|(x, y)| Point2::new(x, y)))
+        }),
+        seed in any::<u64>(),
+    ) {
+        let sensors = layout(max_xy, gap, seed);
+        prop_assert_eq!(brute_force_gaps(&sensors, max_xy), [gap]);
+        prop_assert_eq!(find_distress_beacon(&sensors, max_xy), Some(gap));
+        for y in -2..=max_xy + 2 {
+            prop_assert_eq!(count_impossible(&sensors, y), brute_force_count(&sensors, y));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use common::{Answer, ParseError, Solution};
+
+pub mod plan;
+pub mod valves;
+
+use plan::{Plan, Scenario};
+use valves::Valves;
+
+const START_VALVE: &str = "AA";
+const PART1_TIME: u32 = 30;
+const PART2_TIME: u32 = 26;
+
+fn parse_valves(input: &str) -> Result<Valves, ParseError> {
+    let valves: Valves = input.parse()?;
+    if valves.index(START_VALVE).is_none() {
+        return Err(ParseError::missing(
+            input,
+            format!("valve `{}`", START_VALVE),
+        ));
+    }
+    Ok(valves)
+}
+
+fn plan(valves: &Valves, agents: usize, time: u32) -> Plan {
+    valves
+        .best_plan(&Scenario::new(agents, time))
+        .expect("parsing checks for the start valve")
+}
+
+/// Opening the valves alone.
+fn solo_plan(valves: &Valves) -> Plan {
+    plan(valves, 1, PART1_TIME)
+}
+
+/// Opening the valves with the elephant, after taking the time to teach it how.
+fn plan_with_elephant(valves: &Valves) -> Plan {
+    plan(valves, 2, PART2_TIME)
+}
+
+#[derive(Default)]
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Valves;
+
+    fn parse(&self, input: &str) -> Result<Valves, ParseError> {
+        parse_valves(input)
+    }
+
+    fn problem1(&self, valves: &Valves) -> Answer {
+        solo_plan(valves).pressure.into()
+    }
+
+    fn problem2(&self, valves: &Valves) -> Answer {
+        plan_with_elephant(valves).pressure.into()
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    solo_plan(&parse_valves(input).unwrap()).pressure
+}
+
+pub fn problem2(input: &str) -> u32 {
+    plan_with_elephant(&parse_valves(input).unwrap()).pressure
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 1651);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 1707);
+}
+
+#[test]
+fn test_plan() {
+    let valves = parse_valves(include_str!("../data/sample.txt")).unwrap();
+    let plan = plan_with_elephant(&valves);
+    let opened: Vec<(usize, &str, u32)> = plan
+        .openings
+        .iter()
+        .map(|o| (o.agent, o.valve.as_str(), o.minute))
+        .collect();
+    let elephant = opened[0].0;
+    let me = 1 - elephant;
+    // the walkthrough in the puzzle
+    assert_eq!(
+        opened,
+        [
+            (elephant, "DD", 2),
+            (me, "JJ", 3),
+            (elephant, "HH", 7),
+            (me, "BB", 7),
+            (me, "CC", 9),
+            (elephant, "EE", 11),
+        ]
+    );
+    assert_eq!(plan.openings.iter().map(|o| o.pressure).sum::<u32>(), 1707);
+}
+
+#[test]
+fn test_scenarios() {
+    let valves = parse_valves(include_str!("../data/sample.txt")).unwrap();
+    let best = |scenario: Scenario| valves.best_plan(&scenario).unwrap().pressure;
+    // a third agent gets to some of the valves sooner
+    assert!(best(Scenario::new(3, PART2_TIME)) > 1707);
+    assert_eq!(best(Scenario::new(1, 0)), 0);
+    assert!(best(Scenario::new(1, PART1_TIME).with_start("JJ")) > 1651);
+    assert!(best(Scenario::new(1, PART1_TIME).with_open_duration("DD", 5)) < 1651);
+    assert_eq!(
+        valves.best_plan(&Scenario::new(1, PART1_TIME).with_start("ZZ")),
+        Err(plan::ScenarioError::UnknownValve("ZZ".to_string()))
+    );
+}
@@ -0,0 +1,249 @@
+use std::{cmp::Reverse, collections::HashMap, fmt::Display};
+
+use common::BitSet;
+
+use crate::{valves::Valves, START_VALVE};
+
+/// Settings for a what-if analysis: how many agents work the valves, where they start, how long
+/// they have and how long each valve takes to open.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scenario {
+    pub agents: usize,
+    pub start: String,
+    pub time: u32,
+    /// Minutes it takes to open a valve, for valves that don't take the usual one minute.
+    pub open_durations: HashMap<String, u32>,
+}
+
+impl Scenario {
+    /// `agents` starting at valve `AA` with `time` minutes, opening every valve in a minute.
+    pub fn new(agents: usize, time: u32) -> Self {
+        Self {
+            agents,
+            start: START_VALVE.to_string(),
+            time,
+            open_durations: HashMap::new(),
+        }
+    }
+
+    pub fn with_start(mut self, valve: &str) -> Self {
+        self.start = valve.to_string();
+        self
+    }
+
+    pub fn with_open_duration(mut self, valve: &str, minutes: u32) -> Self {
+        self.open_durations.insert(valve.to_string(), minutes);
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScenarioError {
+    UnknownValve(String),
+}
+
+impl Display for ScenarioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScenarioError::UnknownValve(name) => write!(f, "There is no valve {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+/// One valve being opened as part of a [`Plan`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opening {
+    /// Which agent opens the valve, counting from 0.
+    pub agent: usize,
+    pub valve: String,
+    /// Minutes passed when the valve is open and starts releasing pressure.
+    pub minute: u32,
+    /// Pressure the valve releases until the time is up.
+    pub pressure: u32,
+}
+
+/// The best way to release pressure in a [`Scenario`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub pressure: u32,
+    /// Every valve opened, in the order they open.
+    pub openings: Vec<Opening>,
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for opening in &self.openings {
+            writeln!(
+                f,
+                "Minute {:>2}: agent {} opens {} releasing {}",
+                opening.minute, opening.agent, opening.valve, opening.pressure
+            )?;
+        }
+        write!(f, "Total pressure released: {}", self.pressure)
+    }
+}
+
+/// The scenario with names looked up, and the valves to open in the order a single agent opens
+/// them.
+struct Search<'a> {
+    valves: &'a Valves,
+    start: usize,
+    time: u32,
+    /// Minutes to open each valve, by valve index.
+    open_durations: Vec<u32>,
+    /// The most pressure one agent releases by opening exactly each set of working valves, with
+    /// the order to open them in.
+    best: HashMap<BitSet, (u32, Vec<usize>)>,
+}
+
+impl Search<'_> {
+    /// Walks to every valve that can still be opened in time, recording the most pressure
+    /// released for each set of valves opened along the way.
+    fn explore(
+        &mut self,
+        valve: usize,
+        time_remaining: u32,
+        opened: BitSet,
+        pressure: u32,
+        route: &mut Vec<usize>,
+    ) {
+        let best = self.best.entry(opened).or_default();
+        if pressure > best.0 || opened.is_empty() {
+            *best = (pressure, route.clone());
+        }
+
+        for bit in self.valves.all_working().difference(opened).iter() {
+            let next = self.valves.working[bit];
+            let time_taken = self.valves.distances[valve][next] + self.open_durations[next];
+            if time_taken < time_remaining {
+                let time_open = time_remaining - time_taken;
+                route.push(bit);
+                self.explore(
+                    next,
+                    time_open,
+                    opened.with(bit),
+                    pressure + time_open * self.valves.flow_rates[next] as u32,
+                    route,
+                );
+                route.pop();
+            }
+        }
+    }
+
+    /// What an agent opening the working valves in `route` does, minute by minute.
+    fn openings(&self, agent: usize, route: &[usize]) -> Vec<Opening> {
+        let mut position = self.start;
+        let mut minute = 0;
+        let mut openings = Vec::new();
+        for &bit in route {
+            let valve = self.valves.working[bit];
+            minute += self.valves.distances[position][valve] + self.open_durations[valve];
+            openings.push(Opening {
+                agent,
+                valve: self.valves.names[valve].clone(),
+                minute,
+                pressure: (self.time - minute) * self.valves.flow_rates[valve] as u32,
+            });
+            position = valve;
+        }
+        openings
+    }
+}
+
+/// Picks up to `agents` more disjoint sets from `sets[from..]`, where the sets are sorted by
+/// decreasing pressure, improving on `best` if that releases more pressure.
+fn combine(
+    sets: &[(BitSet, u32)],
+    from: usize,
+    agents: usize,
+    used: BitSet,
+    pressure: u32,
+    chosen: &mut Vec<usize>,
+    best: &mut (u32, Vec<usize>),
+) {
+    if pressure > best.0 {
+        *best = (pressure, chosen.clone());
+    }
+    if agents == 0 {
+        return;
+    }
+    for (i, &(set, set_pressure)) in sets.iter().enumerate().skip(from) {
+        // no later set releases more, so the remaining agents can't beat the best any more
+        if pressure + set_pressure * agents as u32 <= best.0 {
+            break;
+        }
+        if used.is_disjoint(set) {
+            chosen.push(i);
+            combine(
+                sets,
+                i + 1,
+                agents - 1,
+                used.union(set),
+                pressure + set_pressure,
+                chosen,
+                best,
+            );
+            chosen.pop();
+        }
+    }
+}
+
+impl Valves {
+    /// The plan that releases the most pressure in `scenario`. Agents work independently, so
+    /// each agent's best route for every set of valves is found once and the agents then share
+    /// out the valves in disjoint sets.
+    pub fn best_plan(&self, scenario: &Scenario) -> Result<Plan, ScenarioError> {
+        let index = |name: &str| {
+            self.index(name)
+                .ok_or_else(|| ScenarioError::UnknownValve(name.to_string()))
+        };
+        let mut open_durations = vec![1; self.names.len()];
+        for (name, &minutes) in &scenario.open_durations {
+            open_durations[index(name)?] = minutes;
+        }
+        let mut search = Search {
+            valves: self,
+            start: index(&scenario.start)?,
+            time: scenario.time,
+            open_durations,
+            best: HashMap::new(),
+        };
+        search.explore(
+            search.start,
+            scenario.time,
+            BitSet::new(),
+            0,
+            &mut Vec::new(),
+        );
+
+        let mut sets: Vec<(BitSet, u32)> = search
+            .best
+            .iter()
+            .map(|(&set, &(pressure, _))| (set, pressure))
+            .collect();
+        sets.sort_unstable_by_key(|&(set, pressure)| (Reverse(pressure), set));
+        let mut best = (0, Vec::new());
+        combine(
+            &sets,
+            0,
+            scenario.agents,
+            BitSet::new(),
+            0,
+            &mut Vec::new(),
+            &mut best,
+        );
+
+        let mut openings = Vec::new();
+        for (agent, &i) in best.1.iter().enumerate() {
+            let (_, route) = &search.best[&sets[i].0];
+            openings.extend(search.openings(agent, route));
+        }
+        openings.sort_by_key(|opening| (opening.minute, opening.agent));
+        Ok(Plan {
+            pressure: best.0,
+            openings,
+        })
+    }
+}
@@ -0,0 +1,213 @@
+use std::{collections::HashMap, str::FromStr};
+
+use common::{parse_number, BitSet, ParseError};
+
+/// Stands in for "no path" while the shortest paths are worked out.
+const UNREACHABLE: u32 = u32::MAX / 2;
+
+/// One line of the scan: a valve, its flow rate and the valves its tunnels lead to.
+struct Scan<'a> {
+    name: &'a str,
+    flow_rate: u16,
+    tunnels: Vec<&'a str>,
+}
+
+fn next_word<'a>(
+    words: &mut impl Iterator<Item = &'a str>,
+    line: &str,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    words
+        .next()
+        .ok_or_else(|| ParseError::missing(line, expected))
+}
+
+/// Checks that the next word is one of `keywords`.
+fn expect_word<'a>(
+    words: &mut impl Iterator<Item = &'a str>,
+    line: &str,
+    keywords: &[&str],
+) -> Result<(), ParseError> {
+    let expected = format!("`{}`", keywords.join("` or `"));
+    let word = next_word(words, line, &expected)?;
+    if keywords.contains(&word) {
+        Ok(())
+    } else {
+        Err(ParseError::new(line, word, expected))
+    }
+}
+
+fn parse_scan(line: &str) -> Result<Scan<'_>, ParseError> {
+    let mut words = line.split_whitespace();
+    expect_word(&mut words, line, &["Valve"])?;
+    let name = next_word(&mut words, line, "a valve name")?;
+    expect_word(&mut words, line, &["has"])?;
+    expect_word(&mut words, line, &["flow"])?;
+    let rate = next_word(&mut words, line, "`rate=`")?;
+    let flow_rate = match rate.strip_prefix("rate=").and_then(|r| r.strip_suffix(';')) {
+        Some(number) => parse_number(line, number)?,
+        None => return Err(ParseError::new(line, rate, "`rate=` and a number")),
+    };
+    expect_word(&mut words, line, &["tunnels", "tunnel"])?;
+    expect_word(&mut words, line, &["lead", "leads"])?;
+    expect_word(&mut words, line, &["to"])?;
+    expect_word(&mut words, line, &["valves", "valve"])?;
+
+    let tunnels: Vec<&str> = words.map(|n| n.strip_suffix(',').unwrap_or(n)).collect();
+    if tunnels.is_empty() {
+        return Err(ParseError::missing(line, "a valve name"));
+    }
+    Ok(Scan {
+        name,
+        flow_rate,
+        tunnels,
+    })
+}
+
+/// The tunnel network boiled down to the valves worth opening.
+///
+/// Walking the tunnels one minute at a time wastes most of the search on valves with no flow, so
+/// the network is compressed to the length of the shortest walk between every two valves, and
+/// searches only ever walk to the valves with a flow rate. Those are numbered separately so that
+/// working valve `i` is bit `i` in sets of open valves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Valves {
+    pub names: Vec<String>,
+    pub flow_rates: Vec<u16>,
+    /// Minutes it takes to walk from one valve to another.
+    pub distances: Vec<Vec<u32>>,
+    /// The valves with a flow rate.
+    pub working: Vec<usize>,
+}
+
+impl Valves {
+    /// The set of every working valve.
+    pub fn all_working(&self) -> BitSet {
+        BitSet::full(self.working.len())
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+}
+
+/// Shortest walks between every two valves, where `neighbors[i]` are the valves next to valve
+/// `i`.
+fn floyd_warshall(neighbors: &[Vec<usize>]) -> Vec<Vec<u32>> {
+    let n = neighbors.len();
+    let mut distances = vec![vec![UNREACHABLE; n]; n];
+    for (i, tunnels) in neighbors.iter().enumerate() {
+        distances[i][i] = 0;
+        for &j in tunnels {
+            distances[i][j] = 1;
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                let through_k = distances[i][k] + distances[k][j];
+                if through_k < distances[i][j] {
+                    distances[i][j] = through_k;
+                }
+            }
+        }
+    }
+    distances
+}
+
+impl FromStr for Valves {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let scans: Vec<Scan> = s
+            .lines()
+            .map(|line| parse_scan(line).map_err(|e| e.within(s, line)))
+            .collect::<Result<_, _>>()?;
+        let mut indices: HashMap<&str, usize> = HashMap::new();
+        for (i, scan) in scans.iter().enumerate() {
+            if indices.insert(scan.name, i).is_some() {
+                return Err(ParseError::new(
+                    s,
+                    scan.name,
+                    "a valve that isn't scanned yet",
+                ));
+            }
+        }
+
+        let mut neighbors = Vec::new();
+        for scan in &scans {
+            let tunnels = scan
+                .tunnels
+                .iter()
+                .map(|&name| {
+                    indices
+                        .get(name)
+                        .copied()
+                        .ok_or_else(|| ParseError::new(s, name, "the name of a scanned valve"))
+                })
+                .collect::<Result<_, _>>()?;
+            neighbors.push(tunnels);
+        }
+
+        let working: Vec<usize> = (0..scans.len())
+            .filter(|&i| scans[i].flow_rate > 0)
+            .collect();
+        if working.len() > BitSet::CAPACITY {
+            let extra = scans[working[BitSet::CAPACITY]].name;
+            return Err(ParseError::new(
+                s,
+                extra,
+                format!("at most {} valves with a flow rate", BitSet::CAPACITY),
+            ));
+        }
+
+        Ok(Self {
+            names: scans.iter().map(|scan| scan.name.to_string()).collect(),
+            flow_rates: scans.iter().map(|scan| scan.flow_rate).collect(),
+            distances: floyd_warshall(&neighbors),
+            working,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression() {
+        let valves: Valves = include_str!("../data/sample.txt").parse().unwrap();
+        assert_eq!(valves.working.len(), 6);
+        let distance = |from: &str, to: &str| {
+            valves.distances[valves.index(from).unwrap()][valves.index(to).unwrap()]
+        };
+        assert_eq!(distance("AA", "DD"), 1);
+        assert_eq!(distance("AA", "HH"), 5);
+        assert_eq!(distance("HH", "JJ"), 7);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB\n\
+                     Valve BB has flow rate=x; tunnel leads to valve AA\n";
+        let err = input.parse::<Valves>().unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 24, "x"));
+
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=3; tunnel leads to valve AA\n";
+        let err = input.parse::<Valves>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 54: expected the name of a scanned valve, found `CC`"
+        );
+
+        let input = "Valve AA has flow rate=0; tunnel leads to valve BB\n\
+                     Valve BB has flow rate=3; tunnel leads to valve AA\n\
+                     Valve AA has flow rate=5; tunnel leads to valve BB\n";
+        let err = input.parse::<Valves>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 7: expected a valve that isn't scanned yet, found `AA`"
+        );
+    }
+}
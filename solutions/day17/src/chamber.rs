@@ -1,13 +1,17 @@
 use std::{collections::HashSet, fmt::Display};
 
-use crate::{coordinate::Coordinate, rock::Rock};
+use common::Point2;
+
+use crate::rock::Rock;
 
 const WIDTH: usize = 9;
 const AIR_ROW: [bool; WIDTH] = [true, false, false, false, false, false, false, false, true];
 const SNAPSHOT_ROWS: usize = 50;
 
-pub type Snapshot = [bool; SNAPSHOT_ROWS * WIDTH];
+/// The top rows of the chamber, one byte per row with a bit for every cell between the walls.
+pub type Snapshot = [u8; SNAPSHOT_ROWS];
 
+#[derive(Clone)]
 pub struct Chamber {
     grid: Vec<[bool; WIDTH]>,
     rock: Rock,
@@ -33,12 +37,18 @@ impl Display for Chamber {
                 };
                 grid_string.push_str(ch);
             }
-            grid_string.push_str("\n");
+            grid_string.push('\n');
         }
         write!(f, "{}", grid_string)
     }
 }
 
+impl Default for Chamber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Chamber {
     pub fn new() -> Self {
         let grid = vec![[true; WIDTH]];
@@ -52,11 +62,11 @@ impl Chamber {
     }
 
     pub fn get_snapshot(&self) -> Snapshot {
-        let mut snapshot = [false; SNAPSHOT_ROWS * WIDTH];
+        let mut snapshot = [0; SNAPSHOT_ROWS];
         for (i, row) in self.grid.iter().rev().enumerate().take(SNAPSHOT_ROWS) {
-            for (j, c) in row.iter().enumerate() {
-                snapshot[i * WIDTH + j] = *c;
-            }
+            snapshot[i] = row[1..WIDTH - 1]
+                .iter()
+                .fold(0, |bits, &c| bits << 1 | c as u8);
         }
         snapshot
     }
@@ -79,12 +89,16 @@ impl Chamber {
         }
     }
 
-    fn move_rock(&mut self, dx: isize, dy: isize) -> bool {
-        let new_coords: HashSet<Coordinate> = self
+    /// Moves the rock by `offset`, where `y` grows upwards.
+    fn move_rock(&mut self, offset: Point2<i64>) -> bool {
+        let new_coords: HashSet<Point2<usize>> = self
             .rock
             .coordinates
             .iter()
-            .map(|c| c.translate_new(dx, dy))
+            .map(|c| {
+                c.step(offset)
+                    .expect("the walls and the floor keep rocks inside the chamber")
+            })
             .collect();
 
         // if there is a collision at a coordinate
@@ -115,14 +129,14 @@ impl Chamber {
     }
 
     pub fn move_rock_right(&mut self) {
-        self.move_rock(1, 0);
+        self.move_rock(Point2::new(1, 0));
     }
 
     pub fn move_rock_left(&mut self) {
-        self.move_rock(-1, 0);
+        self.move_rock(Point2::new(-1, 0));
     }
 
     pub fn move_rock_down(&mut self) -> bool {
-        self.move_rock(0, -1)
+        self.move_rock(Point2::new(0, -1))
     }
 }
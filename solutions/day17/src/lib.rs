@@ -0,0 +1,147 @@
+use common::{Answer, CycleDetector, ParseError, Solution};
+
+use chamber::Chamber;
+
+const PART1_DROPS: usize = 2022;
+const PART2_DROPS: usize = 1_000_000_000_000;
+
+pub mod chamber;
+pub mod rock;
+
+#[derive(Default)]
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Vec<Jet>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Jet>, ParseError> {
+        let mut lines = input.lines();
+        let pattern = lines.next().unwrap_or_default();
+        if let Some(line) = lines.find(|line| !line.is_empty()) {
+            return Err(ParseError::new(
+                input,
+                line,
+                "the jet pattern to be on one line",
+            ));
+        }
+        let jets = pattern
+            .char_indices()
+            .map(|(i, c)| match c {
+                '<' => Ok(Jet::Left),
+                '>' => Ok(Jet::Right),
+                _ => Err(ParseError::new(
+                    input,
+                    &pattern[i..i + c.len_utf8()],
+                    "`<` or `>`",
+                )),
+            })
+            .collect::<Result<Vec<Jet>, ParseError>>()?;
+        if jets.is_empty() {
+            return Err(ParseError::missing(pattern, "`<` or `>`"));
+        }
+        Ok(jets)
+    }
+
+    fn problem1(&self, jets: &Vec<Jet>) -> Answer {
+        drop_rocks(jets, PART1_DROPS).into()
+    }
+
+    fn problem2(&self, jets: &Vec<Jet>) -> Answer {
+        drop_rocks(jets, PART2_DROPS).into()
+    }
+}
+
+pub fn problem1(input: &str) -> usize {
+    drop_rocks(&Day17.parse(input).unwrap(), PART1_DROPS)
+}
+
+pub fn problem2(input: &str) -> usize {
+    drop_rocks(&Day17.parse(input).unwrap(), PART2_DROPS)
+}
+
+/// Which way a jet of hot gas pushes the falling rock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// The chamber after some rocks have fallen, with how far through the jet pattern it got.
+#[derive(Clone)]
+struct Simulation {
+    chamber: Chamber,
+    rocks_dropped: usize,
+    jet: usize,
+}
+
+/// Parsing makes sure there is at least one jet.
+fn drop_rocks(jets: &[Jet], num_rocks: usize) -> usize {
+    let drop_rock = |simulation: &mut Simulation| {
+        let chamber = &mut simulation.chamber;
+        chamber.start_dropping_rock(simulation.rocks_dropped);
+        simulation.rocks_dropped += 1;
+        loop {
+            match jets[simulation.jet] {
+                Jet::Right => chamber.move_rock_right(),
+                Jet::Left => chamber.move_rock_left(),
+            }
+            simulation.jet = (simulation.jet + 1) % jets.len();
+            if !chamber.move_rock_down() {
+                break;
+            }
+        }
+    };
+    let simulation = Simulation {
+        chamber: Chamber::new(),
+        rocks_dropped: 0,
+        jet: 0,
+    };
+    CycleDetector::new(
+        simulation,
+        drop_rock,
+        // the next rock and jet matter as much as the shape of the top of the tower
+        |simulation| {
+            (
+                simulation.rocks_dropped % 5,
+                simulation.jet,
+                simulation.chamber.get_snapshot(),
+            )
+        },
+        |simulation| simulation.chamber.top() as i64,
+    )
+    .metric_at(num_rocks)
+    .expect("the tower starts repeating itself") as usize
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 3068);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 1514285714288);
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |input: &str| Day17.parse(input).unwrap_err().to_string();
+    assert_eq!(
+        error("A Q\n"),
+        "line 1, column 1: expected `<` or `>`, found `A`"
+    );
+    assert_eq!(
+        error(""),
+        "line 1, column 1: expected `<` or `>`, found the end of the line"
+    );
+    assert_eq!(
+        error("<>\n<>\n"),
+        "line 2, column 1: expected the jet pattern to be on one line, found `<>`"
+    );
+}
@@ -1,11 +1,12 @@
 use std::collections::HashSet;
 
-use crate::coordinate::Coordinate;
+use common::Point2;
 
 const START_X: usize = 3;
 
+#[derive(Clone)]
 pub struct Rock {
-    pub coordinates: HashSet<Coordinate>,
+    pub coordinates: HashSet<Point2<usize>>,
 }
 
 impl Rock {
@@ -15,40 +16,31 @@ impl Rock {
     pub fn new(rocks_dropped: usize, bottom: usize) -> Self {
         let coordinates = match rocks_dropped % 5 {
             0 => (START_X..=START_X + 3)
-                .map(|x| Coordinate::new(x, bottom))
+                .map(|x| Point2::new(x, bottom))
                 .collect(),
-            1 => HashSet::from_iter(
-                vec![
-                    Coordinate::new(START_X + 1, bottom),
-                    Coordinate::new(START_X, bottom + 1),
-                    Coordinate::new(START_X + 1, bottom + 1),
-                    Coordinate::new(START_X + 2, bottom + 1),
-                    Coordinate::new(START_X + 1, bottom + 2),
-                ]
-                .into_iter(),
-            ),
-            2 => HashSet::from_iter(
-                vec![
-                    Coordinate::new(START_X, bottom),
-                    Coordinate::new(START_X + 1, bottom),
-                    Coordinate::new(START_X + 2, bottom),
-                    Coordinate::new(START_X + 2, bottom + 1),
-                    Coordinate::new(START_X + 2, bottom + 2),
-                ]
-                .into_iter(),
-            ),
+            1 => HashSet::from_iter(vec![
+                Point2::new(START_X + 1, bottom),
+                Point2::new(START_X, bottom + 1),
+                Point2::new(START_X + 1, bottom + 1),
+                Point2::new(START_X + 2, bottom + 1),
+                Point2::new(START_X + 1, bottom + 2),
+            ]),
+            2 => HashSet::from_iter(vec![
+                Point2::new(START_X, bottom),
+                Point2::new(START_X + 1, bottom),
+                Point2::new(START_X + 2, bottom),
+                Point2::new(START_X + 2, bottom + 1),
+                Point2::new(START_X + 2, bottom + 2),
+            ]),
             3 => (0..=3)
-                .map(|y_offset| Coordinate::new(START_X, bottom + y_offset))
+                .map(|y_offset| Point2::new(START_X, bottom + y_offset))
                 .collect(),
-            4 => HashSet::from_iter(
-                vec![
-                    Coordinate::new(START_X, bottom),
-                    Coordinate::new(START_X + 1, bottom),
-                    Coordinate::new(START_X, bottom + 1),
-                    Coordinate::new(START_X + 1, bottom + 1),
-                ]
-                .into_iter(),
-            ),
+            4 => HashSet::from_iter(vec![
+                Point2::new(START_X, bottom),
+                Point2::new(START_X + 1, bottom),
+                Point2::new(START_X, bottom + 1),
+                Point2::new(START_X + 1, bottom + 1),
+            ]),
             _ => unreachable!(),
         };
 
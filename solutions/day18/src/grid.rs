@@ -1,16 +1,17 @@
 use std::collections::HashSet;
 
-use crate::coordinate::Coordinate;
+use common::{parse_lines, ParseError, Point3};
+
+type Coordinate = Point3<i8>;
 
 pub struct Grid {
     coordinates: HashSet<Coordinate>,
 }
 
 impl Grid {
-    pub fn new(input: &str) -> Self {
-        let coordinates: HashSet<Coordinate> =
-            input.lines().map(|line| line.parse().unwrap()).collect();
-        Self { coordinates }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let coordinates = parse_lines(input, str::parse)?.into_iter().collect();
+        Ok(Self { coordinates })
     }
 
     pub fn area_including_air_pockets(&self) -> usize {
@@ -27,7 +28,7 @@ impl Grid {
     fn area(coordinates: &HashSet<Coordinate>) -> usize {
         coordinates
             .iter()
-            .flat_map(|c| c.adjacent_coordinates())
+            .flat_map(|c| c.neighbors6())
             .filter(|c| !coordinates.contains(c))
             .count()
     }
@@ -42,22 +43,18 @@ impl Grid {
 
         let mut pockets: HashSet<Coordinate> = (min_x - 1..=max_x + 1)
             .flat_map(|x| (min_y - 1..=max_y + 1).map(move |y| (x, y)))
-            .flat_map(|(x, y)| (min_z - 1..=max_z + 1).map(move |z| Coordinate { x, y, z }))
+            .flat_map(|(x, y)| (min_z - 1..=max_z + 1).map(move |z| Point3::new(x, y, z)))
             .filter(|coord| !self.coordinates.contains(coord))
             .collect();
 
-        let mut cubes_to_explore = vec![Coordinate {
-            x: min_x - 1,
-            y: min_y - 1,
-            z: min_z - 1,
-        }];
+        let mut cubes_to_explore = vec![Point3::new(min_x - 1, min_y - 1, min_z - 1)];
 
         // Simulate "being the water"
         while let Some(cube) = cubes_to_explore.pop() {
             // if the water is able to reach the cube, then it isn't an air pocket
             if pockets.remove(&cube) {
                 // continue exploring along the space
-                cubes_to_explore.extend(cube.adjacent_coordinates());
+                cubes_to_explore.extend(cube.neighbors6());
             }
         }
 
@@ -0,0 +1,47 @@
+use common::{Answer, ParseError, Solution};
+use grid::Grid;
+
+pub mod grid;
+
+#[derive(Default)]
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Grid;
+
+    fn parse(&self, input: &str) -> Result<Grid, ParseError> {
+        Grid::new(input)
+    }
+
+    fn problem1(&self, grid: &Grid) -> Answer {
+        grid.area_including_air_pockets().into()
+    }
+
+    fn problem2(&self, grid: &Grid) -> Answer {
+        grid.area_excluding_air_pockets().into()
+    }
+}
+
+pub fn problem1(input: &str) -> usize {
+    Grid::new(input).unwrap().area_including_air_pockets()
+}
+
+pub fn problem2(input: &str) -> usize {
+    Grid::new(input).unwrap().area_excluding_air_pockets()
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 64);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 58);
+}
@@ -1,32 +1,5 @@
-use grid::Grid;
-
-pub mod coordinate;
-pub mod grid;
+use day18::Day18;
 
 fn main() {
-    let input = include_str!("../data/input.txt");
-    println!("Problem 1: {}", problem1(input));
-    println!("Problem 2: {}", problem2(input));
-}
-
-fn problem1(input: &str) -> usize {
-    Grid::new(input).area_including_air_pockets()
-}
-
-fn problem2(input: &str) -> usize {
-    Grid::new(input).area_excluding_air_pockets()
-}
-
-#[test]
-fn test_problem1() {
-    let input = include_str!("../data/sample.txt");
-    let res = problem1(input);
-    assert_eq!(res, 64);
-}
-
-#[test]
-fn test_problem2() {
-    let input = include_str!("../data/sample.txt");
-    let res = problem2(input);
-    assert_eq!(res, 58);
+    common::main::<Day18>();
 }
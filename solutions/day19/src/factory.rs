@@ -1,116 +1,188 @@
-use std::ops::{AddAssign, SubAssign};
-
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub enum Robot {
-    Ore,
-    Clay,
-    Obsidian,
-    Geode,
-}
+use std::ops::{AddAssign, Index, IndexMut, SubAssign};
+
+use common::BitSet;
+
+/// A resource, numbered by its place in [`Blueprint::resources`]. Robots are known by the
+/// resource they collect.
+pub type Resource = usize;
+
+/// How much there is of a resource, or how many robots collect it. Blueprints cost at most
+/// `u16::MAX` of anything and start with at most that many robots, which leaves room to count
+/// everything collected in [`Blueprint::max_time`] minutes.
+pub type Amount = u32;
 
-#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, Default)]
-pub struct FactoryOutput {
-    pub ore: u16,
-    pub clay: u16,
-    pub obsidian: u16,
-    pub geode: u16,
+/// An amount of every resource.
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Default)]
+pub struct FactoryOutput(pub Vec<Amount>);
+
+impl AddAssign<&FactoryOutput> for FactoryOutput {
+    fn add_assign(&mut self, rhs: &Self) {
+        for (amount, added) in self.0.iter_mut().zip(&rhs.0) {
+            *amount += added;
+        }
+    }
 }
 
-impl AddAssign for FactoryOutput {
-    fn add_assign(&mut self, rhs: Self) {
-        self.ore += rhs.ore;
-        self.clay += rhs.clay;
-        self.obsidian += rhs.obsidian;
-        self.geode += rhs.geode;
+impl SubAssign<&FactoryOutput> for FactoryOutput {
+    fn sub_assign(&mut self, rhs: &Self) {
+        for (amount, spent) in self.0.iter_mut().zip(&rhs.0) {
+            *amount -= spent;
+        }
     }
 }
 
-impl SubAssign for FactoryOutput {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.ore -= rhs.ore;
-        self.clay -= rhs.clay;
-        self.obsidian -= rhs.obsidian;
-        self.geode -= rhs.geode;
+impl Index<Resource> for FactoryOutput {
+    type Output = Amount;
+
+    fn index(&self, resource: Resource) -> &Amount {
+        &self.0[resource]
     }
 }
 
-impl FactoryOutput {
-    pub fn with_ore(mut self, ore: u16) -> Self {
-        self.ore = ore;
-        self
+impl IndexMut<Resource> for FactoryOutput {
+    fn index_mut(&mut self, resource: Resource) -> &mut Amount {
+        &mut self.0[resource]
     }
+}
 
-    pub fn with_clay(mut self, clay: u16) -> Self {
-        self.clay = clay;
-        self
+impl FactoryOutput {
+    /// Nothing of `resources` resources.
+    pub fn empty(resources: usize) -> Self {
+        Self(vec![0; resources])
     }
 
-    pub fn with_obsidian(mut self, obsidian: u16) -> Self {
-        self.obsidian = obsidian;
+    pub fn with(mut self, resource: Resource, amount: Amount) -> Self {
+        self.0[resource] = amount;
         self
     }
 
     pub fn can_afford(&self, other: &Self) -> bool {
-        self.ore >= other.ore
-            && self.clay >= other.clay
-            && self.obsidian >= other.obsidian
-            && self.geode >= other.geode
+        self.0.iter().zip(&other.0).all(|(have, cost)| have >= cost)
     }
 }
 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// The robots in the factory, counted by the resource they collect.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
 pub struct Factory {
-    pub ore_robots: u16,
-    pub clay_robots: u16,
-    pub obsidian_robots: u16,
-    pub geode_robots: u16,
+    pub robots: FactoryOutput,
 }
 
 impl Factory {
-    pub fn produce(&self) -> FactoryOutput {
-        FactoryOutput {
-            ore: self.ore_robots,
-            clay: self.clay_robots,
-            obsidian: self.obsidian_robots,
-            geode: self.geode_robots,
-        }
+    /// What the robots collect in a minute.
+    pub fn produce(&self) -> &FactoryOutput {
+        &self.robots
     }
-}
 
-impl Default for Factory {
-    fn default() -> Self {
-        Self {
-            ore_robots: 1,
-            clay_robots: 0,
-            obsidian_robots: 0,
-            geode_robots: 0,
-        }
+    pub fn count(&self, robot: Resource) -> Amount {
+        self.robots[robot]
+    }
+
+    pub fn add(&mut self, robot: Resource) {
+        self.robots[robot] += 1;
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// What every robot costs, and which resource the factory is after.
+///
+/// Any number of resources (up to [`BitSet::CAPACITY`]) works, and every resource has a robot
+/// collecting it that can cost any mix of resources.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Blueprint {
     pub id: u16,
-    pub ore_cost: FactoryOutput,
-    pub clay_cost: FactoryOutput,
-    pub obsidian_cost: FactoryOutput,
-    pub geode_cost: FactoryOutput,
+    /// Names of the resources, like `ore`.
+    pub resources: Vec<String>,
+    /// What the robot collecting each resource costs.
+    pub costs: Vec<FactoryOutput>,
+    /// The resource to end up with as much of as possible.
+    pub objective: Resource,
+    /// The robots the factory starts with.
+    pub start: Factory,
+    /// Whether to build a robot for the objective whenever one can be afforded, without trying
+    /// anything else. It's the usual shortcut for the puzzle, and makes the search faster, but
+    /// building something else first can collect more, so it's off unless asked for.
+    pub greedy: bool,
+    /// The fewest minutes that have to be left when a robot for each resource is built for it
+    /// to help collect the objective, `usize::MAX` if it never does.
+    lead_times: Vec<usize>,
 }
 
 impl Blueprint {
-    pub fn max_ore(&self) -> u16 {
-        self.ore_cost.ore.max(
-            self.clay_cost
-                .ore
-                .max(self.obsidian_cost.ore.max(self.geode_cost.ore)),
-        )
+    pub fn new(
+        id: u16,
+        resources: Vec<String>,
+        costs: Vec<FactoryOutput>,
+        objective: Resource,
+        start: Factory,
+    ) -> Self {
+        assert!(resources.len() <= BitSet::CAPACITY, "too many resources");
+        assert_eq!(costs.len(), resources.len(), "every resource needs a robot");
+        assert!(
+            objective < resources.len(),
+            "the objective is not a resource"
+        );
+
+        // a robot for the objective collects if it's ready with a minute to spare, and a robot
+        // for anything else collects enough for another robot two minutes after it's built
+        let mut lead_times = vec![usize::MAX; resources.len()];
+        lead_times[objective] = 2;
+        for _ in 0..resources.len() {
+            for resource in 0..resources.len() {
+                let Some(lead_time) = (0..resources.len())
+                    .filter(|&robot| costs[robot][resource] > 0 && lead_times[robot] != usize::MAX)
+                    .map(|robot| lead_times[robot] + 2)
+                    .min()
+                else {
+                    continue;
+                };
+                if lead_time < lead_times[resource] {
+                    lead_times[resource] = lead_time;
+                }
+            }
+        }
+
+        Self {
+            id,
+            resources,
+            costs,
+            objective,
+            start,
+            greedy: false,
+            lead_times,
+        }
     }
-    pub fn max_clay(&self) -> u16 {
-        self.obsidian_cost.clay
+
+    pub fn cost(&self, robot: Resource) -> &FactoryOutput {
+        &self.costs[robot]
+    }
+
+    /// The most of `resource` that can be spent in a minute.
+    pub fn max_spend(&self, resource: Resource) -> Amount {
+        self.costs
+            .iter()
+            .map(|cost| cost[resource])
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The most minutes the factory can run for without counting past [`Amount::MAX`]. There
+    /// can't be more robots than at the start plus one a minute, and nothing is collected any
+    /// faster than that.
+    pub fn max_time(&self) -> usize {
+        let start = self.start.robots.0.iter().copied().max().unwrap_or(0) as u64;
+        let fits = |time: u64| time * (start + time) <= Amount::MAX as u64;
+        let mut time = 0;
+        while fits(time + 1) {
+            time += 1;
+        }
+        time as usize
+    }
+
+    pub fn lead_time(&self, robot: Resource) -> usize {
+        self.lead_times[robot]
     }
 
-    pub fn max_obsidian(&self) -> u16 {
-        self.geode_cost.obsidian
+    pub fn resource(&self, name: &str) -> Option<Resource> {
+        self.resources.iter().position(|r| r == name)
     }
 }
 
@@ -120,8 +192,8 @@ mod tests {
 
     #[test]
     fn test_can_afford() {
-        let output1 = FactoryOutput::default().with_ore(2).with_obsidian(7);
-        let output2 = FactoryOutput::default().with_ore(2).with_clay(1);
-        assert_eq!(false, output2.can_afford(&output1));
+        let output1 = FactoryOutput::empty(4).with(0, 2).with(2, 7);
+        let output2 = FactoryOutput::empty(4).with(0, 2).with(1, 1);
+        assert!(!output2.can_afford(&output1));
     }
 }
@@ -1,225 +1,361 @@
-use std::{collections::HashMap, str::FromStr};
+use std::str::FromStr;
 
-use crate::factory::{Blueprint, Factory, FactoryOutput, Robot};
+use common::{parse_number, BitSet, Memo, ParseError};
+use tracing::{debug, trace};
+
+use crate::{
+    factory::{Amount, Blueprint, Factory, FactoryOutput, Resource},
+    schedule::Schedule,
+};
 
 const PART1_TIME: usize = 24;
 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct GameState {
     factory: Factory,
     resources: FactoryOutput,
     minutes_passed: u16,
-    incoming_robot: Option<Robot>,
+    incoming_robot: Option<Resource>,
+    /// Robots that could have been built last minute but weren't. Building one now would only
+    /// be the same as building it then, a minute later, so it stays off the table until
+    /// another robot is built.
+    skipped: BitSet,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Game {
     blueprint: Blueprint,
     state: GameState,
 }
 
-impl Game {
-    pub fn max_num_geodes(&self, time: usize) -> u16 {
-        let mut max_seen: Vec<u16> = vec![0; time + 1];
-        let optimal = Self::find_optimal(self.clone(), time, &mut HashMap::new(), &mut max_seen);
-        println!("Id: {}, geodes: {}", self.blueprint.id, optimal);
-        optimal
-    }
-    pub fn quality_level(&self) -> u16 {
-        self.blueprint.id * self.max_num_geodes(PART1_TIME)
-    }
+/// The best schedule for a blueprint and how much searching it took to find.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchReport {
+    pub schedule: Schedule,
+    /// Game states the search looked at, including the ones it found in its cache.
+    pub nodes_explored: u64,
+}
 
-    fn should_produce_ore(&self) -> bool {
-        self.state.factory.ore_robots < self.blueprint.max_ore() 
-    }
+/// Branch and bound over the robots to build, remembering the best robot to build (if any) and
+/// the amount of the objective that leads to for every state.
+struct Search<'a> {
+    blueprint: &'a Blueprint,
+    cache: Memo<GameState, (Amount, Option<Resource>)>,
+    /// The most of the objective any schedule found so far collects.
+    best: Amount,
+    nodes_explored: u64,
+}
 
-    fn should_produce_clay(&self) -> bool {
-        self.state.factory.clay_robots < self.blueprint.max_clay() 
+impl<'a> Search<'a> {
+    fn new(blueprint: &'a Blueprint) -> Self {
+        Self {
+            blueprint,
+            cache: Memo::new("day 19 geodes"),
+            best: 0,
+            nodes_explored: 0,
+        }
     }
 
-    fn should_produce_obsidian(&self) -> bool {
-        self.state.factory.obsidian_robots < self.blueprint.max_obsidian() 
+    /// The most of the objective that can be collected from `state` in `time_remaining`
+    /// minutes.
+    fn find_optimal(&mut self, mut state: GameState, time_remaining: usize) -> Amount {
+        self.nodes_explored += 1;
+        state.minutes_passed += 1;
+
+        if let Some((cached_result, _)) = self.cache.get(&state) {
+            return cached_result;
+        }
+
+        let key = state.clone();
+        state.deliver_robot();
+        let optimal = self.choose(&state, time_remaining);
+        self.best = self.best.max(optimal.0);
+        self.cache.insert(key, optimal);
+        optimal.0
     }
 
-    fn collect_resources(&mut self) {
-        self.state.resources += self.state.factory.produce();
+    fn choose(&mut self, state: &GameState, time_remaining: usize) -> (Amount, Option<Resource>) {
+        let blueprint = self.blueprint;
+        let objective = blueprint.objective;
+        if time_remaining == 0 {
+            return (state.resources[objective], None);
+        }
+        if time_remaining == 1 {
+            return (state.advance(blueprint, None).resources[objective], None);
+        }
+        // cannot possibly beat the best
+        if state.upper_bound(blueprint, time_remaining) <= self.best {
+            return (0, None);
+        }
 
-        // throw away extra resources (for better caching)
-        if !self.should_produce_ore() {
-            self.state.resources.ore = self.state.resources.ore.min(self.blueprint.max_ore());
+        let mut optimal = (0, None);
+        let options = state.options(blueprint, time_remaining);
+        for robot in options.iter() {
+            let amount =
+                self.find_optimal(state.advance(blueprint, Some(robot)), time_remaining - 1);
+            if amount > optimal.0 {
+                optimal = (amount, Some(robot));
+            }
         }
 
-        if !self.should_produce_clay() {
-            self.state.resources.clay = self.state.resources.clay.min(self.blueprint.max_clay());
+        // waiting only makes sense to save up for a robot that can't be afforded yet
+        let affordable = state.affordable(blueprint);
+        let saving_up = (0..blueprint.resources.len()).any(|robot| {
+            state.worth_building(blueprint, robot, time_remaining) && !affordable.contains(robot)
+        });
+        if saving_up || options.is_empty() {
+            let amount = self.find_optimal(state.advance(blueprint, None), time_remaining - 1);
+            if amount > optimal.0 {
+                optimal = (amount, None);
+            }
         }
+        optimal
+    }
 
-        if !self.should_produce_obsidian() {
-            self.state.resources.obsidian = self.state.resources.obsidian.min(self.blueprint.max_obsidian());
+    /// Follows the robots remembered in the cache from `state`, which gives the order to build
+    /// robots in to collect the most of the objective. States the search pruned collect no
+    /// more than the best schedule whatever is built, so waiting there is as good as anything.
+    fn replay(&mut self, mut state: GameState, time: usize) -> Schedule {
+        let mut builds = Vec::new();
+        for _ in 0..time {
+            state.minutes_passed += 1;
+            let robot = self.cache.get(&state).and_then(|(_, robot)| robot);
+            state.deliver_robot();
+            state = state.advance(self.blueprint, robot);
+            builds.push(robot);
         }
+        Schedule::new(self.blueprint.clone(), &builds)
+    }
+}
 
+impl Game {
+    pub fn new(blueprint: Blueprint) -> Self {
+        let state = GameState {
+            factory: blueprint.start.clone(),
+            resources: FactoryOutput::empty(blueprint.resources.len()),
+            minutes_passed: 0,
+            incoming_robot: None,
+            skipped: BitSet::new(),
+        };
+        Self { blueprint, state }
     }
 
-    fn deliver_robot(&mut self) {
-        match self.state.incoming_robot {
-            None => (),
-            Some(Robot::Ore) => self.state.factory.ore_robots += 1,
-            Some(Robot::Clay) => self.state.factory.clay_robots += 1,
-            Some(Robot::Obsidian) => self.state.factory.obsidian_robots += 1,
-            Some(Robot::Geode) => self.state.factory.geode_robots += 1,
+    pub fn blueprint(&self) -> &Blueprint {
+        &self.blueprint
+    }
+
+    /// Finds the order to build robots in to collect the most of the objective in `time`
+    /// minutes, which can't be more than [`Blueprint::max_time`].
+    pub fn search(&self, time: usize) -> SearchReport {
+        assert!(
+            time <= self.blueprint.max_time(),
+            "blueprint {} can't count what it collects in {} minutes",
+            self.blueprint.id,
+            time
+        );
+        let mut search = Search::new(&self.blueprint);
+        let optimal = search.find_optimal(self.state.clone(), time);
+        let schedule = search.replay(self.state.clone(), time);
+        debug_assert_eq!(schedule.objective(), optimal);
+        debug!(
+            blueprint = self.blueprint.id,
+            geodes = optimal,
+            nodes_explored = search.nodes_explored,
+            "solved blueprint"
+        );
+        trace!("{}", schedule);
+        SearchReport {
+            schedule,
+            nodes_explored: search.nodes_explored,
         }
-        self.state.incoming_robot = None;
     }
 
-    fn find_optimal(
-        mut game: Self,
+    /// The order to build robots in to collect the most of the objective in `time` minutes.
+    pub fn best_schedule(&self, time: usize) -> Schedule {
+        self.search(time).schedule
+    }
+
+    pub fn max_num_geodes(&self, time: usize) -> Amount {
+        self.best_schedule(time).objective()
+    }
+    pub fn quality_level(&self) -> u64 {
+        self.blueprint.id as u64 * self.max_num_geodes(PART1_TIME) as u64
+    }
+}
+
+impl GameState {
+    /// Whether more robots for `resource` could collect anything that gets spent. There is no
+    /// limit to how much of the objective is useful.
+    fn should_produce(&self, blueprint: &Blueprint, resource: Resource) -> bool {
+        resource == blueprint.objective
+            || self.factory.count(resource) < blueprint.max_spend(resource)
+    }
+
+    /// Whether a robot for `robot` can still help collect the objective: there have to be
+    /// uses for what it collects and enough time left for them.
+    fn worth_building(
+        &self,
+        blueprint: &Blueprint,
+        robot: Resource,
         time_remaining: usize,
-        cache: &mut HashMap<GameState, u16>,
-        max_seen: &mut Vec<u16>,
-    ) -> u16 {
-        game.state.minutes_passed += 1;
-        let key = game.state.clone();
-
-        if let Some(cached_result) = cache.get(&game.state) {
-            return *cached_result;
-        }
+    ) -> bool {
+        self.should_produce(blueprint, robot) && time_remaining >= blueprint.lead_time(robot)
+    }
 
-        game.deliver_robot();
-        let mut optimal: Option<u16> = None;
-        if time_remaining == 0 {
-            optimal = Some(game.state.resources.geode);
-        } else if time_remaining == 1 {
-            game.collect_resources();
-            optimal = Some(game.state.resources.geode);
-        } else if game.state.resources.can_afford(&game.blueprint.geode_cost) {
-            game.buy_robot(Robot::Geode);
-            game.collect_resources();
-            optimal = Some(Self::find_optimal(
-                game,
-                time_remaining - 1,
-                cache,
-                max_seen,
-            ));
-        }
+    fn affordable(&self, blueprint: &Blueprint) -> BitSet {
+        (0..blueprint.resources.len())
+            .filter(|&robot| self.resources.can_afford(blueprint.cost(robot)))
+            .collect()
+    }
 
-        let highest_possible_geode = game.state.resources.geode as usize + game.state.factory.geode_robots as usize * time_remaining
-                    + ((time_remaining * (time_remaining + 1))
-                    / 2);
+    /// Whether a robot for the objective stays affordable every minute once it is, whatever
+    /// else gets built: the factory collects everything it costs at least as fast as any robot
+    /// spends it. Nothing but those robots adds to the objective, so building one every minute
+    /// from then on collects the most.
+    fn objective_always_affordable(&self, blueprint: &Blueprint) -> bool {
+        let cost = blueprint.cost(blueprint.objective);
+        (0..blueprint.resources.len()).all(|resource| {
+            cost[resource] == 0 || self.factory.count(resource) >= blueprint.max_spend(resource)
+        })
+    }
 
-        if optimal.is_some() 
-            // cannot possibly beat max
-        || highest_possible_geode
-                < max_seen[time_remaining] as usize
+    /// The robots to try building. A robot for the objective is the only one tried when it can
+    /// be afforded and is sure to be the best choice, or whenever it can be afforded if the
+    /// blueprint is [greedy](Blueprint::greedy).
+    fn options(&self, blueprint: &Blueprint, time_remaining: usize) -> BitSet {
+        let affordable = self.affordable(blueprint);
+        let objective = blueprint.objective;
+        if affordable.contains(objective)
+            && (blueprint.greedy || self.objective_always_affordable(blueprint))
         {
-            let optimal = optimal.unwrap_or(0);
-            max_seen[time_remaining] = max_seen[time_remaining].max(optimal);
-            cache.insert(key, optimal);
-            return optimal;
+            return BitSet::new().with(objective);
         }
+        (0..blueprint.resources.len())
+            .filter(|&robot| self.worth_building(blueprint, robot, time_remaining))
+            .filter(|&robot| affordable.contains(robot) && !self.skipped.contains(robot))
+            .collect()
+    }
 
-        let mut optimal = 0;
-
-        // don't bother generating ore factories if you can't spend it fast enough
-        if game.should_produce_ore() && game.state.resources.can_afford(&game.blueprint.ore_cost) {
-            let mut ore_game = game.clone();
-            ore_game.buy_robot(Robot::Ore);
-            ore_game.collect_resources();
-            optimal = optimal.max(Self::find_optimal(
-                ore_game,
-                time_remaining - 1,
-                cache,
-                max_seen,
-            ));
+    /// The most of the objective that could be collected if the factory could build a robot
+    /// for every other resource each minute for free, on top of a robot for the objective
+    /// whenever it can afford one.
+    fn upper_bound(&self, blueprint: &Blueprint, time_remaining: usize) -> Amount {
+        let objective = blueprint.objective;
+        let objective_cost = blueprint.cost(objective);
+        let mut resources = self.resources.clone();
+        let mut robots = self.factory.robots.clone();
+        for _ in 0..time_remaining {
+            let build_objective_robot = resources.can_afford(objective_cost);
+            if build_objective_robot {
+                resources -= objective_cost;
+            }
+            resources += &robots;
+            for robot in 0..blueprint.resources.len() {
+                if robot != objective || build_objective_robot {
+                    robots[robot] += 1;
+                }
+            }
         }
+        resources[objective]
+    }
 
-        // don't bother generating clay factories if you can't spend it fast enough
-        if game.should_produce_clay() && game.state.resources.can_afford(&game.blueprint.clay_cost) {
-            let mut clay_game = game.clone();
-            clay_game.buy_robot(Robot::Clay);
-            clay_game.collect_resources();
-            optimal = optimal.max(Self::find_optimal(
-                clay_game,
-                time_remaining - 1,
-                cache,
-                max_seen,
-            ));
+    /// The state a minute later, after starting to build `robot` (if any) and collecting.
+    fn advance(&self, blueprint: &Blueprint, robot: Option<Resource>) -> Self {
+        let mut state = self.clone();
+        match robot {
+            Some(robot) => {
+                state.buy_robot(blueprint, robot);
+                state.skipped = BitSet::new();
+            }
+            None => state.skipped = self.affordable(blueprint),
         }
+        state.collect_resources(blueprint);
+        state
+    }
 
-        // don't bother generating obsidian factories if you can't spend it fast enough
-        if game.should_produce_obsidian() && game
-            .state
-            .resources
-            .can_afford(&game.blueprint.obsidian_cost)
-        {
-            let mut obsidian_game = game.clone();
-            obsidian_game.buy_robot(Robot::Obsidian);
-            obsidian_game.collect_resources();
-            optimal = optimal.max(Self::find_optimal(
-                obsidian_game,
-                time_remaining - 1,
-                cache,
-                max_seen,
-            ));
+    fn collect_resources(&mut self, blueprint: &Blueprint) {
+        self.resources += self.factory.produce();
+
+        // throw away extra resources (for better caching)
+        for resource in 0..blueprint.resources.len() {
+            if !self.should_produce(blueprint, resource) {
+                let max_spend = blueprint.max_spend(resource);
+                self.resources[resource] = self.resources[resource].min(max_spend);
+            }
         }
+    }
 
-        // do nothing
-        game.collect_resources();
-        optimal = optimal.max(Self::find_optimal(
-            game,
-            time_remaining - 1,
-            cache,
-            max_seen,
-        ));
-
-        max_seen[time_remaining] = max_seen[time_remaining].max(optimal);
-        cache.insert(key, optimal);
-        optimal
+    fn deliver_robot(&mut self) {
+        if let Some(robot) = self.incoming_robot.take() {
+            self.factory.add(robot);
+        }
     }
 
-    fn buy_robot(&mut self, robot: Robot) {
-        match robot {
-            Robot::Ore => {
-                self.state.resources -= self.blueprint.ore_cost;
-            }
-            Robot::Clay => {
-                self.state.resources -= self.blueprint.clay_cost;
-            }
-            Robot::Obsidian => {
-                self.state.resources -= self.blueprint.obsidian_cost;
-            }
-            Robot::Geode => {
-                self.state.resources -= self.blueprint.geode_cost;
+    fn buy_robot(&mut self, blueprint: &Blueprint, robot: Resource) {
+        self.resources -= blueprint.cost(robot);
+        self.incoming_robot = Some(robot);
+    }
+}
+
+/// The resources in the puzzle's blueprints.
+const RESOURCES: [&str; 4] = ["ore", "clay", "obsidian", "geode"];
+
+const BLUEPRINT_FORMAT: &str = "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.";
+
+/// Matches `s` word by word against `format`, returning the numbers in place of the `{}`s.
+fn scan(s: &str, format: &str) -> Result<Vec<u16>, ParseError> {
+    let mut words = s.split_whitespace();
+    let mut values = Vec::new();
+    for expected in format.split_whitespace() {
+        let word = words
+            .next()
+            .ok_or_else(|| ParseError::missing(s, format!("`{}`", expected)))?;
+        match expected.strip_prefix("{}") {
+            Some(suffix) => {
+                let number = word.strip_suffix(suffix).ok_or_else(|| {
+                    ParseError::new(s, word, format!("a number followed by `{}`", suffix))
+                })?;
+                values.push(parse_number(s, number)?);
             }
+            None if word == expected => {}
+            None => return Err(ParseError::new(s, word, format!("`{}`", expected))),
         }
-        self.state.incoming_robot = Some(robot);
+    }
+    match words.next() {
+        Some(word) => Err(ParseError::new(s, word, "the end of the blueprint")),
+        None => Ok(values),
     }
 }
 
 impl FromStr for Game {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian) =
-            sscanf::sscanf!(s,"Blueprint {u16}: Each ore robot costs {u16} ore. Each clay robot costs {u16} ore. Each obsidian robot costs {u16} ore and {u16} clay. Each geode robot costs {u16} ore and {u16} obsidian.").unwrap();
-
-        let ore_cost = FactoryOutput::default().with_ore(ore_ore);
-        let clay_cost = FactoryOutput::default().with_ore(clay_ore);
-        let obsidian_cost = FactoryOutput::default()
-            .with_ore(obsidian_ore)
-            .with_clay(obsidian_clay);
-        let geode_cost = FactoryOutput::default()
-            .with_ore(geode_ore)
-            .with_obsidian(geode_obsidian);
-
-        let blueprint = Blueprint {
-            id,
-            ore_cost,
-            clay_cost,
-            obsidian_cost,
-            geode_cost,
+        let [id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+            scan(s, BLUEPRINT_FORMAT)?[..]
+        else {
+            unreachable!("the format has seven numbers")
         };
 
-        let state = GameState::default();
-        Ok(Self { blueprint, state })
+        let [ore, clay, obsidian, geode] = [0, 1, 2, 3];
+        let cost = || FactoryOutput::empty(RESOURCES.len());
+        let costs = vec![
+            cost().with(ore, ore_ore.into()),
+            cost().with(ore, clay_ore.into()),
+            cost()
+                .with(ore, obsidian_ore.into())
+                .with(clay, obsidian_clay.into()),
+            cost()
+                .with(ore, geode_ore.into())
+                .with(obsidian, geode_obsidian.into()),
+        ];
+        let start = Factory {
+            robots: cost().with(ore, 1),
+        };
+        let resources = RESOURCES.iter().map(|name| name.to_string()).collect();
+        Ok(Self::new(Blueprint::new(
+            id, resources, costs, geode, start,
+        )))
     }
 }
 
@@ -231,52 +367,39 @@ mod tests {
     fn test_game_from_str() {
         let blueprint_str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.";
         let actual: Game = blueprint_str.parse().unwrap();
-        let expected = Game {
-            blueprint: Blueprint {
-                id: 1,
-                ore_cost: FactoryOutput {
-                    ore: 4,
-                    clay: 0,
-                    obsidian: 0,
-                    geode: 0,
-                },
-                clay_cost: FactoryOutput {
-                    ore: 2,
-                    clay: 0,
-                    obsidian: 0,
-                    geode: 0,
-                },
-                obsidian_cost: FactoryOutput {
-                    ore: 3,
-                    clay: 14,
-                    obsidian: 0,
-                    geode: 0,
-                },
-                geode_cost: FactoryOutput {
-                    ore: 2,
-                    clay: 0,
-                    obsidian: 7,
-                    geode: 0,
-                },
-            },
-            state: GameState {
-                factory: Factory {
-                    ore_robots: 1,
-                    clay_robots: 0,
-                    obsidian_robots: 0,
-                    geode_robots: 0,
-                },
-                resources: FactoryOutput {
-                    ore: 0,
-                    clay: 0,
-                    obsidian: 0,
-                    geode: 0,
-                },
-                minutes_passed: 0,
-                incoming_robot: None,
-            },
-        };
+        let blueprint = actual.blueprint();
+        assert_eq!(blueprint.id, 1);
+        assert_eq!(blueprint.resources, RESOURCES);
+        assert_eq!(
+            blueprint.costs,
+            [
+                FactoryOutput(vec![4, 0, 0, 0]),
+                FactoryOutput(vec![2, 0, 0, 0]),
+                FactoryOutput(vec![3, 14, 0, 0]),
+                FactoryOutput(vec![2, 0, 7, 0]),
+            ]
+        );
+        assert_eq!(blueprint.objective, 3);
+        assert_eq!(blueprint.start.robots, FactoryOutput(vec![1, 0, 0, 0]));
+        assert_eq!(
+            (0..4).map(|r| blueprint.lead_time(r)).collect::<Vec<_>>(),
+            [4, 6, 4, 2]
+        );
+        assert_eq!(actual.state.resources, FactoryOutput(vec![0; 4]));
+    }
 
-        assert_eq!(expected, actual);
+    #[test]
+    fn test_pruning() {
+        let game: Game = include_str!("../data/sample.txt")
+            .lines()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(game.state.upper_bound(&game.blueprint, PART1_TIME) >= 9);
+        let report = game.search(PART1_TIME);
+        assert_eq!(report.schedule.objective(), 9);
+        // without the pruning the search looks at millions of states
+        assert!(report.nodes_explored < 10_000, "{}", report.nodes_explored);
     }
 }
@@ -0,0 +1,155 @@
+use common::{parse_lines, Answer, ParseError, Solution};
+use game::Game;
+
+pub mod factory;
+pub mod game;
+pub mod schedule;
+pub mod spec;
+
+use rayon::prelude::*;
+
+const PART2_TIME: usize = 32;
+
+#[derive(Default)]
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = Vec<Game>;
+
+    /// Reads the puzzle's blueprints, or blueprints written out as TOML or JSON.
+    fn parse(&self, input: &str) -> Result<Vec<Game>, ParseError> {
+        if input.trim_start().starts_with("Blueprint") {
+            parse_lines(input, str::parse)
+        } else {
+            spec::parse_spec(input)
+        }
+    }
+
+    fn problem1(&self, games: &Vec<Game>) -> Answer {
+        total_quality_level(games).into()
+    }
+
+    fn problem2(&self, games: &Vec<Game>) -> Answer {
+        geode_product(games).into()
+    }
+}
+
+fn total_quality_level(games: &[Game]) -> u64 {
+    games.par_iter().map(Game::quality_level).sum()
+}
+
+fn geode_product(games: &[Game]) -> u64 {
+    games
+        .par_iter()
+        .take(3)
+        .map(|game| game.max_num_geodes(PART2_TIME) as u64)
+        .product::<u64>()
+}
+
+pub fn problem1(input: &str) -> u64 {
+    total_quality_level(&Day19.parse(input).unwrap())
+}
+
+pub fn problem2(input: &str) -> u64 {
+    geode_product(&Day19.parse(input).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem1() {
+        let input = include_str!("../data/sample.txt");
+        let res = problem1(input);
+        assert_eq!(res, 33);
+    }
+    //
+    #[test]
+    fn test_problem2() {
+        let input = include_str!("../data/sample.txt");
+        let res = problem2(input);
+        assert_eq!(res, 62 * 56);
+    }
+
+    #[test]
+    fn test_schedule() {
+        let games = Day19.parse(include_str!("../data/sample.txt")).unwrap();
+        let schedule = games[0].best_schedule(24);
+        assert_eq!(schedule.objective(), 9);
+        assert_eq!(schedule.steps.len(), 24);
+        let narrative = schedule.to_string();
+        assert!(narrative.starts_with(
+            "== Minute 1 ==\n\
+             1 ore-collecting robot collects 1 ore; you now have 1 ore.\n\
+             \n\
+             == Minute 2 ==\n"
+        ));
+        assert!(narrative.contains("Spend 2 ore to start building a clay-collecting robot."));
+        assert!(narrative
+            .contains("Spend 3 ore and 14 clay to start building an obsidian-collecting robot."));
+        assert!(narrative.contains("\n== Minute 24 ==\n"));
+        assert!(narrative.ends_with("you now have 9 open geodes.\n"));
+    }
+
+    #[test]
+    fn test_spec() {
+        let input = include_str!("../data/sample.toml");
+        assert_eq!(
+            Day19.parse(input).unwrap(),
+            Day19.parse(include_str!("../data/sample.txt")).unwrap()
+        );
+        assert_eq!(problem1(input), 33);
+
+        // wood buys more woodcutters and saws, and the saws turn wood into planks
+        let input = r#"{
+            "blueprints": [{
+                "id": 7,
+                "resources": ["wood", "plank"],
+                "robots": {"wood": {"wood": 2}, "plank": {"wood": 3}}
+            }]
+        }"#;
+        let games = Day19.parse(input).unwrap();
+        let schedule = games[0].best_schedule(8);
+        assert_eq!(schedule.objective(), 5);
+        assert!(schedule
+            .to_string()
+            .contains("Spend 3 wood to start building a plank-collecting robot."));
+    }
+
+    #[test]
+    fn test_greedy() {
+        // a second woodcutter before the first saw pays off, which building a saw whenever one
+        // can be afforded never finds out
+        let spec = |greedy| {
+            format!(
+                r#"{{"blueprints": [{{
+                    "id": 1,
+                    "resources": ["wood", "plank"],
+                    "robots": {{"wood": {{"wood": 2}}, "plank": {{"wood": 2}}}},
+                    "greedy": {}
+                }}]}}"#,
+                greedy
+            )
+        };
+        let planks = |greedy| Day19.parse(&spec(greedy)).unwrap()[0].max_num_geodes(11);
+        assert_eq!(planks(false), 21);
+        assert_eq!(planks(true), 20);
+    }
+
+    #[test]
+    fn test_large_amounts() {
+        let input = r#"{"blueprints": [{
+            "id": 65535,
+            "resources": ["wood", "plank"],
+            "robots": {"wood": {"wood": 65535}, "plank": {"wood": 1}},
+            "start": {"wood": 1, "plank": 65535}
+        }]}"#;
+        let games = Day19.parse(input).unwrap();
+        assert_eq!(games[0].max_num_geodes(PART2_TIME), 65535 * 32 + 465);
+        assert_eq!(problem1(input), 65535 * (65535 * 24 + 253));
+        assert!(games[0].blueprint().max_time() >= PART2_TIME);
+    }
+}
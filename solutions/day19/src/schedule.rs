@@ -0,0 +1,142 @@
+use std::fmt::Display;
+
+use crate::factory::{Amount, Blueprint, Factory, FactoryOutput, Resource};
+
+/// What happens in one minute of a [`Schedule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// Counting from 1.
+    pub minute: usize,
+    /// The robot the factory starts building, ready at the end of the minute.
+    pub built: Option<Resource>,
+    /// The robots collecting during the minute.
+    pub robots: Factory,
+    /// Everything collected so far, at the end of the minute.
+    pub resources: FactoryOutput,
+}
+
+/// The robots to build with a blueprint, minute by minute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    pub blueprint: Blueprint,
+    pub steps: Vec<Step>,
+}
+
+impl Schedule {
+    /// Runs the factory with `builds[i]` the robot (if any) to build in minute `i + 1`.
+    pub fn new(blueprint: Blueprint, builds: &[Option<Resource>]) -> Self {
+        let mut robots = blueprint.start.clone();
+        let mut resources = FactoryOutput::empty(blueprint.resources.len());
+        let mut steps = Vec::new();
+        for (i, &built) in builds.iter().enumerate() {
+            if let Some(robot) = built {
+                resources -= blueprint.cost(robot);
+            }
+            resources += robots.produce();
+            steps.push(Step {
+                minute: i + 1,
+                built,
+                robots: robots.clone(),
+                resources: resources.clone(),
+            });
+            if let Some(robot) = built {
+                robots.add(robot);
+            }
+        }
+        Self { blueprint, steps }
+    }
+
+    /// How much of the objective there is at the end.
+    pub fn objective(&self) -> Amount {
+        self.steps
+            .last()
+            .map_or(0, |step| step.resources[self.blueprint.objective])
+    }
+}
+
+fn plural(n: Amount) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+/// The minute-by-minute account from the puzzle, in its words for geodes.
+impl Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = &self.blueprint.resources;
+        let robot_name = |robot: Resource| match names[robot].as_str() {
+            "geode" => "geode-cracking".to_string(),
+            name => format!("{}-collecting", name),
+        };
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "== Minute {} ==", step.minute)?;
+            if let Some(robot) = step.built {
+                let cost = self.blueprint.cost(robot);
+                let spent: Vec<String> = (0..names.len())
+                    .filter(|&resource| cost[resource] > 0)
+                    .map(|resource| format!("{} {}", cost[resource], names[resource]))
+                    .collect();
+                let name = robot_name(robot);
+                let article = if name.starts_with(['a', 'e', 'i', 'o', 'u']) {
+                    "an"
+                } else {
+                    "a"
+                };
+                writeln!(
+                    f,
+                    "Spend {} to start building {} {} robot.",
+                    spent.join(" and "),
+                    article,
+                    name
+                )?;
+            }
+            for (resource, name) in names.iter().enumerate() {
+                let count = step.robots.count(resource);
+                let total = step.resources[resource];
+                if count == 0 {
+                    continue;
+                }
+                if name == "geode" {
+                    writeln!(
+                        f,
+                        "{} geode-cracking robot{} crack{} {} geode{}; you now have {} open geode{}.",
+                        count,
+                        plural(count),
+                        if count == 1 { "s" } else { "" },
+                        count,
+                        plural(count),
+                        total,
+                        plural(total)
+                    )?;
+                } else {
+                    writeln!(
+                        f,
+                        "{} {} robot{} collect{} {} {}; you now have {} {}.",
+                        count,
+                        robot_name(resource),
+                        plural(count),
+                        if count == 1 { "s" } else { "" },
+                        count,
+                        name,
+                        total,
+                        name
+                    )?;
+                }
+            }
+            if let Some(robot) = step.built {
+                writeln!(
+                    f,
+                    "The new {} robot is ready; you now have {} of them.",
+                    robot_name(robot),
+                    step.robots.count(robot) + 1
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+//! Blueprints written out as TOML or JSON, for factories that aren't limited to the puzzle's
+//! four resources:
+//!
+//! ```toml
+//! [[blueprints]]
+//! id = 1
+//! resources = ["ore", "clay", "obsidian", "geode"]
+//! # the last resource if left out
+//! objective = "geode"
+//! # one robot for the first resource if left out
+//! start = { ore = 1 }
+//! # build a robot for the objective whenever one can be afforded, which is faster but can miss
+//! # the best schedule; false if left out
+//! greedy = false
+//!
+//! [blueprints.robots]
+//! ore = { ore = 4 }
+//! clay = { ore = 2 }
+//! obsidian = { ore = 3, clay = 14 }
+//! geode = { ore = 2, obsidian = 7 }
+//! ```
+//!
+//! JSON has the same shape, starting with `{"blueprints": [...]}`.
+
+use std::collections::BTreeMap;
+
+use common::{BitSet, ParseError};
+use serde::Deserialize;
+
+use crate::{
+    factory::{Blueprint, Factory, FactoryOutput},
+    game::Game,
+};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BlueprintSpec {
+    id: u16,
+    resources: Vec<String>,
+    /// What the robot collecting each resource costs, by name.
+    robots: BTreeMap<String, BTreeMap<String, u16>>,
+    objective: Option<String>,
+    start: Option<BTreeMap<String, u16>>,
+    #[serde(default)]
+    greedy: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Spec {
+    blueprints: Vec<BlueprintSpec>,
+}
+
+/// Where `name` is first mentioned in `input` as a whole word. Names read from TOML and JSON
+/// are copies, so errors about them have to find them again.
+fn locate<'a>(input: &'a str, name: &str) -> &'a str {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    input
+        .match_indices(name)
+        .map(|(i, _)| i)
+        .find(|&i| !input[..i].ends_with(is_word) && !input[i + name.len()..].starts_with(is_word))
+        .map_or(&input[input.len()..], |i| &input[i..i + name.len()])
+}
+
+/// The token at byte `offset` of `input`: the word there, or the single character if it isn't
+/// part of one.
+fn token_at(input: &str, offset: usize) -> &str {
+    let rest = &input[offset.min(input.len())..];
+    let word = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+        .unwrap_or(rest.len());
+    match word {
+        0 => &rest[..rest.chars().next().map_or(0, char::len_utf8)],
+        len => &rest[..len],
+    }
+}
+
+/// What serde's `message` says it expected, like `u16` from "invalid type: string "x",
+/// expected u16".
+fn expected(message: &str) -> &str {
+    let message = message.trim();
+    match message.split_once(", expected ") {
+        Some((_, expected)) => expected,
+        None => message.strip_prefix("missing ").unwrap_or(message),
+    }
+}
+
+fn toml_error(input: &str, error: toml::de::Error) -> ParseError {
+    let offset = error.span().map_or(input.len(), |span| span.start);
+    ParseError::new(input, token_at(input, offset), expected(error.message()))
+}
+
+fn json_error(input: &str, error: serde_json::Error) -> ParseError {
+    let line_start: usize = input
+        .split_inclusive('\n')
+        .take(error.line().saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let offset = line_start + error.column().saturating_sub(1);
+    // serde_json puts its position in its message as well
+    let message = error.to_string();
+    let message = message.split(" at line ").next().unwrap_or_default();
+    ParseError::new(input, token_at(input, offset), expected(message))
+}
+
+/// Amounts by resource name, as a [`FactoryOutput`].
+fn amounts(
+    input: &str,
+    blueprint: &BlueprintSpec,
+    amounts: &BTreeMap<String, u16>,
+) -> Result<FactoryOutput, ParseError> {
+    let mut output = FactoryOutput::empty(blueprint.resources.len());
+    for (name, &amount) in amounts {
+        let resource = blueprint
+            .resources
+            .iter()
+            .position(|r| r == name)
+            .ok_or_else(|| ParseError::new(input, locate(input, name), "one of the resources"))?;
+        output[resource] = amount.into();
+    }
+    Ok(output)
+}
+
+fn blueprint(input: &str, spec: &BlueprintSpec) -> Result<Blueprint, ParseError> {
+    let names = &spec.resources;
+    if names.is_empty() {
+        return Err(ParseError::new(
+            input,
+            locate(input, "resources"),
+            "at least one resource",
+        ));
+    }
+    if names.len() > BitSet::CAPACITY {
+        return Err(ParseError::new(
+            input,
+            locate(input, &names[BitSet::CAPACITY]),
+            format!("at most {} resources", BitSet::CAPACITY),
+        ));
+    }
+    for (i, name) in names.iter().enumerate() {
+        if names[..i].contains(name) {
+            return Err(ParseError::new(
+                input,
+                locate(input, name),
+                "resources with different names",
+            ));
+        }
+    }
+
+    for name in spec.robots.keys() {
+        if !names.contains(name) {
+            return Err(ParseError::new(
+                input,
+                locate(input, name),
+                "a robot for one of the resources",
+            ));
+        }
+    }
+    let costs = names
+        .iter()
+        .map(|name| match spec.robots.get(name) {
+            Some(cost) => amounts(input, spec, cost),
+            None => Err(ParseError::new(
+                input,
+                locate(input, name),
+                format!("a robot collecting `{}`", name),
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let objective = match &spec.objective {
+        Some(name) => names
+            .iter()
+            .position(|r| r == name)
+            .ok_or_else(|| ParseError::new(input, locate(input, name), "one of the resources"))?,
+        None => names.len() - 1,
+    };
+    if costs[objective][objective] > 0 {
+        return Err(ParseError::new(
+            input,
+            locate(input, &names[objective]),
+            "a robot for the objective that doesn't cost the objective",
+        ));
+    }
+
+    let start = match &spec.start {
+        Some(start) => amounts(input, spec, start)?,
+        None => FactoryOutput::empty(names.len()).with(0, 1),
+    };
+    let mut blueprint = Blueprint::new(
+        spec.id,
+        names.clone(),
+        costs,
+        objective,
+        Factory { robots: start },
+    );
+    blueprint.greedy = spec.greedy;
+    Ok(blueprint)
+}
+
+/// Reads blueprints written as JSON if `input` starts with `{`, and as TOML otherwise.
+pub fn parse_spec(input: &str) -> Result<Vec<Game>, ParseError> {
+    let spec: Spec = if input.trim_start().starts_with('{') {
+        serde_json::from_str(input).map_err(|e| json_error(input, e))?
+    } else {
+        toml::from_str(input).map_err(|e| toml_error(input, e))?
+    };
+    spec.blueprints
+        .iter()
+        .map(|spec| blueprint(input, spec).map(Game::new))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_errors() {
+        let toml = "[[blueprints]]\n\
+                    id = 1\n\
+                    resources = [\"wood\", \"gold\"]\n\
+                    robots = { wood = { wood = 2 }, gold = { stone = 3 } }\n";
+        let err = parse_spec(toml).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 42: expected one of the resources, found `stone`"
+        );
+
+        let toml = "[[blueprints]]\nid = 1\nresources = [\"wood\"]\nrobot = {}\n";
+        let err = parse_spec(toml).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 1: expected one of `id`, `resources`, `robots`, `objective`, `start`, \
+             `greedy`, found `robot`"
+        );
+
+        let json = r#"{"blueprints": [{"id": 1, "resources": ["wood", "gold"], "robots": {"gold": {"wood": 3}}}]}"#;
+        let err = parse_spec(json).unwrap_err();
+        assert_eq!(err.expected, "a robot collecting `wood`");
+        assert_eq!(err.column, 42);
+
+        // amounts too large to count are turned down on loading
+        let toml = "[[blueprints]]\nid = 1\nresources = [\"wood\"]\nrobots = { wood = { wood = 70000 } }\n";
+        let err = parse_spec(toml).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 28: expected u16, found `70000`"
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use std::fmt::Display;
+
+use num::{rational::Ratio, One, Zero};
+
+/// Numbers are exact fractions, so dividing never rounds.
+pub type Rational = Ratio<i128>;
+
+/// The name of the unknown in part 2.
+pub const HUMAN: &str = "humn";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    pub fn apply(self, left: Rational, right: Rational) -> Result<Rational, SolveError> {
+        Ok(match self {
+            Operator::Add => left + right,
+            Operator::Subtract => left - right,
+            Operator::Multiply => left * right,
+            Operator::Divide if right.is_zero() => return Err(SolveError::DivisionByZero),
+            Operator::Divide => left / right,
+        })
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+        }
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            Operator::Add | Operator::Subtract => 1,
+            Operator::Multiply | Operator::Divide => 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    DivisionByZero,
+    /// No number makes both sides of the equation equal.
+    NoSolution,
+    /// Every number makes both sides of the equation equal.
+    InfinitelyManySolutions,
+    /// The unknown shows up more than once, so the operations can't just be undone.
+    NotInvertible,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::DivisionByZero => write!(f, "A monkey divides by zero"),
+            SolveError::NoSolution => write!(f, "No number makes {} right", HUMAN),
+            SolveError::InfinitelyManySolutions => {
+                write!(f, "Any number {} yells makes it right", HUMAN)
+            }
+            SolveError::NotInvertible => {
+                write!(f, "{} is used more than once, which can't be undone", HUMAN)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// What a monkey yells, worked out as far as it is known.
+///
+/// The parts of the expression are kept in one list, every operation after its operands and
+/// the whole expression last, so expressions as deep as the longest chain of monkeys can be
+/// cloned, compared, dropped and written out without recursion.
+#[derive(Clone, Debug)]
+pub struct Expr {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Node {
+    Number(Rational),
+    /// The number the human yells.
+    Human,
+    /// An operation on the nodes at two earlier positions, which no other operation uses.
+    Operation(usize, Operator, usize),
+}
+
+impl Expr {
+    pub fn number(n: Rational) -> Self {
+        Expr {
+            nodes: vec![Node::Number(n)],
+        }
+    }
+
+    pub fn human() -> Self {
+        Expr {
+            nodes: vec![Node::Human],
+        }
+    }
+
+    /// Adds the nodes of the smaller operand to those of the larger one, so building a chain
+    /// takes time in proportion to its length whichever side it grows on.
+    pub fn operation(left: Expr, operator: Operator, right: Expr) -> Self {
+        let append = |nodes: &mut Vec<Node>, other: Vec<Node>| {
+            let offset = nodes.len();
+            nodes.extend(other.into_iter().map(|node| match node {
+                Node::Operation(l, operator, r) => {
+                    Node::Operation(l + offset, operator, r + offset)
+                }
+                node => node,
+            }));
+            nodes.len() - 1
+        };
+        let (mut nodes, left, right) = if left.nodes.len() >= right.nodes.len() {
+            let mut nodes = left.nodes;
+            let left = nodes.len() - 1;
+            let right = append(&mut nodes, right.nodes);
+            (nodes, left, right)
+        } else {
+            let mut nodes = right.nodes;
+            let right = nodes.len() - 1;
+            let left = append(&mut nodes, left.nodes);
+            (nodes, left, right)
+        };
+        nodes.push(Node::Operation(left, operator, right));
+        Expr { nodes }
+    }
+
+    fn root(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    fn node_value(&self, node: usize) -> Option<Rational> {
+        match self.nodes[node] {
+            Node::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn value(&self) -> Option<Rational> {
+        self.node_value(self.root())
+    }
+
+    /// Works out everything that doesn't depend on the human, and drops operations that
+    /// don't change anything, like adding 0. Multiplying by 0 drops the human as well.
+    pub fn simplify(self) -> Result<Expr, SolveError> {
+        // the simplified expression for every node, until the operation using it takes it
+        let mut simplified: Vec<Option<Expr>> = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes {
+            let expr = match node {
+                Node::Operation(left, operator, right) => {
+                    let mut take =
+                        |node: usize| simplified[node].take().expect("nodes are used once");
+                    let (left, right) = (take(left), take(right));
+                    Expr::combine(left, operator, right)?
+                }
+                node => Expr { nodes: vec![node] },
+            };
+            simplified.push(Some(expr));
+        }
+        Ok(simplified.pop().flatten().expect("expressions have a node"))
+    }
+
+    /// `left operator right`, simplified as far as it goes if `left` and `right` already are.
+    pub fn combine(left: Expr, operator: Operator, right: Expr) -> Result<Expr, SolveError> {
+        let zero = Some(Rational::zero());
+        let one = Some(Rational::one());
+        Ok(match (left.value(), operator, right.value()) {
+            (Some(l), _, Some(r)) => Expr::number(operator.apply(l, r)?),
+            (_, Operator::Divide, r) if r == zero => return Err(SolveError::DivisionByZero),
+            (l, Operator::Multiply, _) | (_, Operator::Multiply, l) if l == zero => {
+                Expr::number(Rational::zero())
+            }
+            (l, Operator::Add, _) if l == zero => right,
+            (l, Operator::Multiply, _) if l == one => right,
+            (_, Operator::Add | Operator::Subtract, r) if r == zero => left,
+            (_, Operator::Multiply | Operator::Divide, r) if r == one => left,
+            _ => Expr::operation(left, operator, right),
+        })
+    }
+}
+
+/// Expressions are equal if they do the same operations, however their nodes are laid out.
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        let mut pairs = vec![(self.root(), other.root())];
+        while let Some((a, b)) = pairs.pop() {
+            match (self.nodes[a], other.nodes[b]) {
+                (Node::Operation(l1, o1, r1), Node::Operation(l2, o2, r2)) if o1 == o2 => {
+                    pairs.extend([(l1, l2), (r1, r2)]);
+                }
+                (Node::Number(x), Node::Number(y)) if x == y => {}
+                (Node::Human, Node::Human) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Eq for Expr {}
+
+/// What is left to write out of an expression.
+enum Piece {
+    Node { node: usize, parenthesize: bool },
+    Operator(Operator),
+    Close,
+}
+
+/// Writes operations with as few parentheses as it takes to keep their meaning.
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precedence = |node: usize| match self.nodes[node] {
+            Node::Operation(_, operator, _) => operator.precedence(),
+            _ => u8::MAX,
+        };
+        let mut pieces = vec![Piece::Node {
+            node: self.root(),
+            parenthesize: false,
+        }];
+        while let Some(piece) = pieces.pop() {
+            let (node, parenthesize) = match piece {
+                Piece::Node { node, parenthesize } => (node, parenthesize),
+                Piece::Operator(operator) => {
+                    write!(f, " {} ", operator.symbol())?;
+                    continue;
+                }
+                Piece::Close => {
+                    write!(f, ")")?;
+                    continue;
+                }
+            };
+            if parenthesize {
+                write!(f, "(")?;
+                pieces.push(Piece::Close);
+            }
+            match self.nodes[node] {
+                Node::Number(n) if n.is_integer() => write!(f, "{}", n)?,
+                Node::Number(n) => write!(f, "({})", n)?,
+                Node::Human => write!(f, "{}", HUMAN)?,
+                Node::Operation(left, operator, right) => {
+                    let right_precedence = precedence(right);
+                    pieces.push(Piece::Node {
+                        node: right,
+                        parenthesize: right_precedence < operator.precedence()
+                            || (right_precedence == operator.precedence()
+                                && matches!(operator, Operator::Subtract | Operator::Divide)),
+                    });
+                    pieces.push(Piece::Operator(operator));
+                    pieces.push(Piece::Node {
+                        node: left,
+                        parenthesize: precedence(left) < operator.precedence(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Two things the root monkey checks are equal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Equation {
+    pub left: Expr,
+    pub right: Expr,
+}
+
+impl Display for Equation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.left, self.right)
+    }
+}
+
+impl Equation {
+    pub fn simplify(self) -> Result<Equation, SolveError> {
+        Ok(Equation {
+            left: self.left.simplify()?,
+            right: self.right.simplify()?,
+        })
+    }
+
+    /// The number the human has to yell for both sides of a simplified equation to be equal,
+    /// found by undoing the operations between the human and the root one at a time.
+    pub fn solve(self) -> Result<Rational, SolveError> {
+        let Equation { left, right } = self;
+        match (left.value(), right.value()) {
+            (Some(l), Some(r)) if l == r => Err(SolveError::InfinitelyManySolutions),
+            (Some(_), Some(_)) => Err(SolveError::NoSolution),
+            (None, Some(target)) => undo(&left, target),
+            (Some(target), None) => undo(&right, target),
+            (None, None) => Err(SolveError::NotInvertible),
+        }
+    }
+}
+
+/// The number the human has to yell for the simplified `expr` to come to `target`.
+fn undo(expr: &Expr, mut target: Rational) -> Result<Rational, SolveError> {
+    let mut node = expr.root();
+    loop {
+        let (left, operator, right) = match expr.nodes[node] {
+            Node::Human => return Ok(target),
+            Node::Number(_) => unreachable!("simplifying works out everything without the human"),
+            Node::Operation(left, operator, right) => (left, operator, right),
+        };
+        let zero = Rational::zero();
+        let (l, r) = (expr.node_value(left), expr.node_value(right));
+        (node, target) = match (l, operator, r) {
+            (None, _, None) => return Err(SolveError::NotInvertible),
+            (Some(_), _, Some(_)) => unreachable!("simplifying works out operations on numbers"),
+            (None, Operator::Add, Some(r)) => (left, target - r),
+            (None, Operator::Subtract, Some(r)) => (left, target + r),
+            (None, Operator::Divide, Some(r)) => (left, target * r),
+            (Some(l), Operator::Add, None) => (right, target - l),
+            (Some(l), Operator::Subtract, None) => (right, l - target),
+            (None, Operator::Multiply, Some(c)) | (Some(c), Operator::Multiply, None) => {
+                let unknown = if l.is_none() { left } else { right };
+                match (c == zero, target == zero) {
+                    (true, true) => return Err(SolveError::InfinitelyManySolutions),
+                    (true, false) => return Err(SolveError::NoSolution),
+                    _ => (unknown, target / c),
+                }
+            }
+            // c / x = target
+            (Some(l), Operator::Divide, None) => match (l == zero, target == zero) {
+                (true, true) => return Err(SolveError::InfinitelyManySolutions),
+                (true, false) | (false, true) => return Err(SolveError::NoSolution),
+                (false, false) => (right, l / target),
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(n: i128) -> Expr {
+        Expr::number(Rational::from_integer(n))
+    }
+
+    #[test]
+    fn test_display() {
+        // a - (b - c) needs its parentheses, (a - b) - c doesn't
+        let expr = Expr::operation(
+            Expr::operation(number(1), Operator::Subtract, number(2)),
+            Operator::Subtract,
+            Expr::operation(
+                number(3),
+                Operator::Subtract,
+                Expr::operation(Expr::human(), Operator::Multiply, number(4)),
+            ),
+        );
+        assert_eq!(expr.to_string(), "1 - 2 - (3 - humn * 4)");
+    }
+
+    #[test]
+    fn test_solve() {
+        let solve = |left: Expr, right: i128| -> Result<Rational, SolveError> {
+            Equation {
+                left,
+                right: number(right),
+            }
+            .simplify()?
+            .solve()
+        };
+        // 10 / (humn + 1) = 4
+        let expr = Expr::operation(
+            number(10),
+            Operator::Divide,
+            Expr::operation(Expr::human(), Operator::Add, number(1)),
+        );
+        assert_eq!(solve(expr, 4), Ok(Rational::new(3, 2)));
+
+        let times = |n| Expr::operation(Expr::human(), Operator::Multiply, number(n));
+        assert_eq!(solve(times(0), 0), Err(SolveError::InfinitelyManySolutions));
+        assert_eq!(solve(times(0), 5), Err(SolveError::NoSolution));
+        let twice = Expr::operation(Expr::human(), Operator::Add, Expr::human());
+        assert_eq!(solve(twice, 5), Err(SolveError::NotInvertible));
+        let divided = Expr::operation(Expr::human(), Operator::Divide, number(0));
+        assert_eq!(solve(divided, 5), Err(SolveError::DivisionByZero));
+    }
+}
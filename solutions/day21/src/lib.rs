@@ -0,0 +1,92 @@
+use common::{Answer, ParseError, Solution};
+use tracing::debug;
+
+pub mod expr;
+pub mod monkeys;
+
+use expr::{Rational, SolveError};
+use monkeys::Monkeys;
+
+fn root_number(monkeys: &Monkeys) -> Result<Rational, SolveError> {
+    let expression = monkeys.root_expression(false)?;
+    Ok(expression.value().expect("only the human is ever unknown"))
+}
+
+fn human_number(monkeys: &Monkeys) -> Result<Rational, SolveError> {
+    let equation = monkeys
+        .root_equation()?
+        .expect("the root monkey compares two numbers");
+    debug!("{}", equation);
+    equation.solve()
+}
+
+/// Numbers that aren't whole are written as fractions, and errors in their place.
+fn answer(result: Result<Rational, SolveError>) -> Answer {
+    match result {
+        Ok(n) if n.is_integer() => n.to_integer().into(),
+        Ok(n) => n.to_string().into(),
+        Err(e) => e.to_string().into(),
+    }
+}
+
+#[derive(Default)]
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Monkeys;
+
+    fn parse(&self, input: &str) -> Result<Monkeys, ParseError> {
+        input.parse()
+    }
+
+    fn problem1(&self, monkeys: &Monkeys) -> Answer {
+        answer(root_number(monkeys))
+    }
+
+    fn problem2(&self, monkeys: &Monkeys) -> Answer {
+        answer(human_number(monkeys))
+    }
+}
+
+pub fn problem1(input: &str) -> Rational {
+    root_number(&input.parse().unwrap()).unwrap()
+}
+
+pub fn problem2(input: &str) -> Rational {
+    human_number(&input.parse().unwrap()).unwrap()
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, Rational::from_integer(152));
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, Rational::from_integer(301));
+}
+
+#[test]
+fn test_equation() {
+    let input = include_str!("../data/sample.txt");
+    let monkeys: Monkeys = input.parse().unwrap();
+    let equation = monkeys.root_equation().unwrap().unwrap();
+    assert_eq!(equation.to_string(), "(4 + 2 * (humn - 3)) / 4 = 150");
+
+    // make the human's number irrelevant, then contradictory
+    let input = input.replace("ljgn: 2", "ljgn: 0");
+    let monkeys: Monkeys = input.parse().unwrap();
+    assert_eq!(human_number(&monkeys), Err(SolveError::NoSolution));
+    let input = input.replace("sllz: 4", "sllz: 600");
+    let monkeys: Monkeys = input.parse().unwrap();
+    assert_eq!(
+        human_number(&monkeys),
+        Err(SolveError::InfinitelyManySolutions)
+    );
+}
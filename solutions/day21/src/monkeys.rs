@@ -0,0 +1,307 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    str::FromStr,
+};
+
+use common::{parse_number, ParseError};
+
+use crate::expr::{Equation, Expr, Operator, Rational, SolveError, HUMAN};
+
+/// The monkey every other monkey's number ends up with.
+pub const ROOT: &str = "root";
+
+/// What a monkey does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Job<Name = String> {
+    Yell(i128),
+    /// Yells the result of an operation on what two other monkeys yell.
+    Operation(Name, Operator, Name),
+}
+
+impl<Name> Job<Name> {
+    /// The monkeys this one waits for.
+    pub fn operands(&self) -> Vec<&Name> {
+        match self {
+            Job::Yell(_) => vec![],
+            Job::Operation(left, _, right) => vec![left, right],
+        }
+    }
+}
+
+fn parse_job(line: &str) -> Result<(&str, Job<&str>), ParseError> {
+    let (name, job) = line
+        .split_once(": ")
+        .ok_or_else(|| ParseError::missing(line, "`: ` after the monkey's name"))?;
+    let words: Vec<&str> = job.split_whitespace().collect();
+    let job = match words[..] {
+        [number] => Job::Yell(parse_number(line, number)?),
+        [left, operator, right] => {
+            let operator = match operator {
+                "+" => Operator::Add,
+                "-" => Operator::Subtract,
+                "*" => Operator::Multiply,
+                "/" => Operator::Divide,
+                _ => return Err(ParseError::new(line, operator, "`+`, `-`, `*` or `/`")),
+            };
+            Job::Operation(left, operator, right)
+        }
+        [] => return Err(ParseError::missing(line, "a number or an operation")),
+        [_, _] => return Err(ParseError::missing(line, "the name of a monkey")),
+        [.., extra] => return Err(ParseError::new(line, extra, "the end of the line")),
+    };
+    Ok((name, job))
+}
+
+/// Orders the monkeys `root` waits for so that every monkey comes after the monkeys it waits
+/// for, walking the jobs without recursion so long chains of monkeys can't overflow the stack.
+fn topological_order<'a>(
+    input: &str,
+    jobs: &HashMap<&'a str, Job<&'a str>>,
+) -> Result<Vec<&'a str>, ParseError> {
+    // monkeys being visited, with the next of their operands to visit
+    let mut stack: Vec<(&str, usize)> = vec![(ROOT, 0)];
+    let mut done: HashMap<&str, bool> = HashMap::from([(ROOT, false)]);
+    let mut order = Vec::new();
+    while let Some((name, next)) = stack.last_mut() {
+        let operands = jobs[*name].operands();
+        let Some(&&operand) = operands.get(*next) else {
+            done.insert(name, true);
+            order.push(*name);
+            stack.pop();
+            continue;
+        };
+        *next += 1;
+        match done.get(operand) {
+            None => {
+                done.insert(operand, false);
+                stack.push((operand, 0));
+            }
+            Some(false) => {
+                let start = stack.iter().position(|&(n, _)| n == operand).unwrap();
+                let cycle: Vec<&str> = stack[start..].iter().map(|&(n, _)| n).collect();
+                return Err(ParseError::new(
+                    input,
+                    operand,
+                    format!(
+                        "a monkey that isn't waiting for itself through `{} -> {}`",
+                        cycle.join(" -> "),
+                        operand
+                    ),
+                ));
+            }
+            Some(true) => {}
+        }
+    }
+    Ok(order)
+}
+
+/// Every monkey's job, checked to make sense: every monkey waited for exists, no monkey ends
+/// up waiting for itself and `root` waits for every monkey.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Monkeys {
+    jobs: HashMap<String, Job>,
+    /// Every monkey, after the monkeys it waits for.
+    order: Vec<String>,
+}
+
+impl FromStr for Monkeys {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parsed: Vec<(&str, Job<&str>)> = s
+            .lines()
+            .map(|line| parse_job(line).map_err(|e| e.within(s, line)))
+            .collect::<Result<_, _>>()?;
+        let mut jobs = HashMap::new();
+        for (name, job) in &parsed {
+            match jobs.entry(*name) {
+                Entry::Occupied(_) => {
+                    return Err(ParseError::new(s, name, "the name of a new monkey"))
+                }
+                Entry::Vacant(entry) => entry.insert(job.clone()),
+            };
+        }
+        for (_, job) in &parsed {
+            for &operand in job.operands() {
+                if !jobs.contains_key(operand) {
+                    return Err(ParseError::new(s, operand, "the name of a monkey"));
+                }
+            }
+        }
+        if !jobs.contains_key(ROOT) {
+            return Err(ParseError::missing(s, format!("monkey `{}`", ROOT)));
+        }
+
+        let order = topological_order(s, &jobs)?;
+        if order.len() < jobs.len() {
+            let reached: HashSet<&str> = order.iter().copied().collect();
+            let (unreachable, _) = parsed
+                .iter()
+                .find(|(name, _)| !reached.contains(name))
+                .expect("some monkey wasn't reached");
+            return Err(ParseError::new(
+                s,
+                unreachable,
+                format!("a monkey `{}` waits for", ROOT),
+            ));
+        }
+
+        let to_string = |job: &Job<&str>| match *job {
+            Job::Yell(number) => Job::Yell(number),
+            Job::Operation(left, operator, right) => {
+                Job::Operation(left.to_string(), operator, right.to_string())
+            }
+        };
+        Ok(Self {
+            jobs: jobs
+                .iter()
+                .map(|(name, job)| (name.to_string(), to_string(job)))
+                .collect(),
+            order: order.into_iter().map(str::to_string).collect(),
+        })
+    }
+}
+
+impl Monkeys {
+    pub fn job(&self, name: &str) -> Option<&Job> {
+        self.jobs.get(name)
+    }
+
+    /// What every monkey yells, except monkey `skip`, with the human left as an unknown if
+    /// `human_unknown` is set. Works through the monkeys in order rather than recursively.
+    fn expressions(
+        &self,
+        human_unknown: bool,
+        skip: &str,
+    ) -> Result<HashMap<&str, Expr>, SolveError> {
+        // monkeys waiting for each monkey, so the last one can take its expression
+        let mut listeners: HashMap<&str, usize> = HashMap::new();
+        for job in self.jobs.values() {
+            for operand in job.operands() {
+                *listeners.entry(operand).or_default() += 1;
+            }
+        }
+        let mut expressions: HashMap<&str, Expr> = HashMap::new();
+        for name in &self.order {
+            if name == skip {
+                continue;
+            }
+            let expression = match &self.jobs[name] {
+                _ if human_unknown && name == HUMAN => Expr::human(),
+                Job::Yell(number) => Expr::number(Rational::from_integer(*number)),
+                Job::Operation(left, operator, right) => {
+                    let mut take = |operand: &str| {
+                        let count = listeners.get_mut(operand).expect("operands have listeners");
+                        *count -= 1;
+                        if *count == 0 {
+                            expressions.remove(operand).expect("operands come first")
+                        } else {
+                            expressions[operand].clone()
+                        }
+                    };
+                    let (left, right) = (take(left), take(right));
+                    Expr::combine(left, *operator, right)?
+                }
+            };
+            expressions.insert(name, expression);
+        }
+        Ok(expressions)
+    }
+
+    /// What `root` yells, worked out as far as it goes with the human left as an unknown if
+    /// `human_unknown` is set.
+    pub fn root_expression(&self, human_unknown: bool) -> Result<Expr, SolveError> {
+        let mut expressions = self.expressions(human_unknown, "")?;
+        Ok(expressions.remove(ROOT).expect("root is always worked out"))
+    }
+
+    /// What `root` checks when it compares what two other monkeys yell, simplified, with the
+    /// human as the unknown. `None` if `root` just yells a number.
+    pub fn root_equation(&self) -> Result<Option<Equation>, SolveError> {
+        let Job::Operation(left, _, right) = &self.jobs[ROOT] else {
+            return Ok(None);
+        };
+        let mut expressions = self.expressions(true, ROOT)?;
+        let left = expressions
+            .remove(left.as_str())
+            .expect("root's operands are worked out");
+        // root can compare a monkey with itself
+        let right = expressions
+            .remove(right.as_str())
+            .unwrap_or_else(|| left.clone());
+        Ok(Some(Equation { left, right }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "root: pppw + sjmn\nsjmn: 5 % 3\n"
+            .parse::<Monkeys>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 9: expected `+`, `-`, `*` or `/`, found `%`"
+        );
+        let err = "root: pppw +\n".parse::<Monkeys>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, 13));
+    }
+
+    #[test]
+    fn test_graph_errors() {
+        let error = |input: &str| input.parse::<Monkeys>().unwrap_err().to_string();
+        assert_eq!(
+            error("root: aaaa + bbbb\naaaa: 1\nbbbb: aaab * 2\n"),
+            "line 3, column 7: expected the name of a monkey, found `aaab`"
+        );
+        assert_eq!(
+            error("root: aaaa + bbbb\naaaa: 1\nbbbb: 2\naaaa: 3\n"),
+            "line 4, column 1: expected the name of a new monkey, found `aaaa`"
+        );
+        assert_eq!(
+            error("aaaa: 1\n"),
+            "line 2, column 1: expected monkey `root`, found the end of the line"
+        );
+        assert_eq!(
+            error("root: aaaa + bbbb\naaaa: 1\nbbbb: cccc - aaaa\ncccc: bbbb / aaaa\n"),
+            "line 4, column 7: expected a monkey that isn't waiting for itself through \
+             `bbbb -> cccc -> bbbb`, found `bbbb`"
+        );
+        assert_eq!(
+            error("root: aaaa + bbbb\naaaa: 1\nbbbb: 2\ncccc: 3\n"),
+            "line 4, column 1: expected a monkey `root` waits for, found `cccc`"
+        );
+    }
+
+    #[test]
+    fn test_long_chain() {
+        // a chain far too long to work out recursively
+        let mut input = String::from("root: m0 + one\none: 1\n");
+        for i in 0..100_000 {
+            input.push_str(&format!("m{}: m{} + one\n", i, i + 1));
+        }
+        input.push_str("m100000: 1\n");
+        let monkeys: Monkeys = input.parse().unwrap();
+        assert_eq!(
+            monkeys.root_expression(false),
+            Ok(Expr::number(Rational::from_integer(100_002)))
+        );
+
+        // and with the human at the end of it
+        let input = input.replace("m100000: 1\n", "m100000: humn * one\nhumn: 5\n");
+        let monkeys: Monkeys = input.parse().unwrap();
+        assert_eq!(
+            monkeys.root_expression(false),
+            Ok(Expr::number(Rational::from_integer(100_006)))
+        );
+        let equation = monkeys.root_equation().unwrap().unwrap();
+        assert_eq!(
+            equation.to_string(),
+            format!("{}{} = 1", HUMAN, " + 1".repeat(100_000))
+        );
+        assert_eq!(equation.solve(), Ok(Rational::from_integer(-99_999)));
+    }
+}
@@ -0,0 +1,351 @@
+//! Folding the board into a cube for part 2, whichever way it is unfolded.
+
+use std::collections::VecDeque;
+
+use common::{grid::SparsePosition, Direction4, ParseError, Point2, Point3, SparseGrid};
+
+type Vector = Point3<i64>;
+
+/// The 11 ways to unfold a cube, up to turning and flipping them over, drawn with a `#` per
+/// face.
+pub const NETS: [&str; 11] = [
+    "#...\n####\n#...",
+    "#...\n####\n.#..",
+    "#...\n####\n..#.",
+    "#...\n####\n...#",
+    ".#..\n####\n.#..",
+    ".#..\n####\n..#.",
+    "##..\n.###\n.#..",
+    "##..\n.###\n..#.",
+    "##..\n.###\n...#",
+    "##..\n.##.\n..##",
+    "###..\n..###",
+];
+
+/// A square of the board, and which way it faces once the board is folded.
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    /// The top left tile of the face on the board.
+    origin: SparsePosition,
+    /// Where right and down on the board point on the cube.
+    right: Vector,
+    down: Vector,
+    /// Which way the face looks out of the cube.
+    normal: Vector,
+}
+
+impl Face {
+    /// Where `direction` on the board points on the cube.
+    fn towards(&self, direction: Direction4) -> Vector {
+        match direction {
+            Direction4::Up => -self.down,
+            Direction4::Right => self.right,
+            Direction4::Down => self.down,
+            Direction4::Left => -self.right,
+        }
+    }
+
+    /// The face at `origin`, next to this one in `direction` on the board, folded over the edge
+    /// between them. It looks out where the edge led, and walking on over the edge heads back
+    /// past this face.
+    fn fold(&self, direction: Direction4, origin: SparsePosition) -> Face {
+        let mut face = Face {
+            origin,
+            normal: self.towards(direction),
+            ..*self
+        };
+        match direction {
+            Direction4::Up => face.down = self.normal,
+            Direction4::Right => face.right = -self.normal,
+            Direction4::Down => face.down = -self.normal,
+            Direction4::Left => face.right = self.normal,
+        }
+        face
+    }
+}
+
+/// How far the tile at `local` on a face is along its edge in `direction`, going clockwise
+/// around the face. Two faces sharing an edge go along it the opposite ways.
+fn along_edge(local: SparsePosition, direction: Direction4, size: i64) -> i64 {
+    match direction {
+        Direction4::Up => local.x,
+        Direction4::Right => local.y,
+        Direction4::Down => size - 1 - local.x,
+        Direction4::Left => size - 1 - local.y,
+    }
+}
+
+/// The tile `offset` along the edge of a face in `direction`, undoing [`along_edge`].
+fn on_edge(offset: i64, direction: Direction4, size: i64) -> SparsePosition {
+    match direction {
+        Direction4::Up => Point2::new(offset, 0),
+        Direction4::Right => Point2::new(size - 1, offset),
+        Direction4::Down => Point2::new(size - 1 - offset, size - 1),
+        Direction4::Left => Point2::new(0, size - 1 - offset),
+    }
+}
+
+/// Where the faces of a net are, counted in faces from the top left, turned and flipped into
+/// the same position whichever way round it's drawn.
+fn canonical(faces: &[Point2<i64>]) -> Vec<Point2<i64>> {
+    let transforms: [fn(Point2<i64>) -> Point2<i64>; 8] = [
+        |p| p,
+        |p| Point2::new(-p.x, p.y),
+        |p| Point2::new(p.x, -p.y),
+        |p| Point2::new(-p.x, -p.y),
+        |p| Point2::new(p.y, p.x),
+        |p| Point2::new(-p.y, p.x),
+        |p| Point2::new(p.y, -p.x),
+        |p| Point2::new(-p.y, -p.x),
+    ];
+    transforms
+        .iter()
+        .map(|transform| {
+            let moved: Vec<_> = faces.iter().map(|&p| transform(p)).collect();
+            let min_x = moved.iter().map(|p| p.x).min().unwrap_or_default();
+            let min_y = moved.iter().map(|p| p.y).min().unwrap_or_default();
+            let mut shape: Vec<_> = moved
+                .iter()
+                .map(|p| Point2::new(p.x - min_x, p.y - min_y))
+                .collect();
+            shape.sort();
+            shape
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn net_faces(net: &str) -> Vec<Point2<i64>> {
+    net.lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.char_indices()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(x, _)| Point2::new(x as i64, y as i64))
+        })
+        .collect()
+}
+
+/// The board folded into a cube, knowing which edges end up glued together.
+#[derive(Clone, Debug)]
+pub struct Cube {
+    size: i64,
+    faces: Vec<Face>,
+    /// Which of [`NETS`] the board is.
+    net: usize,
+}
+
+impl Cube {
+    /// Folds the `tiles` of `map`: six squares of the same size, joined edge to edge in the
+    /// shape of one of the [`NETS`].
+    pub fn fold<T>(map: &str, tiles: &SparseGrid<T>) -> Result<Self, ParseError> {
+        let size = (1..)
+            .map(|size| (size, 6 * size * size))
+            .find(|&(_, area)| area >= tiles.len())
+            .filter(|&(_, area)| area == tiles.len())
+            .map(|(size, _)| size as i64)
+            .ok_or_else(|| {
+                ParseError::missing(map, "six square faces of the same size for the cube")
+            })?;
+        let (min, _) = tiles.bounds().expect("the board has tiles");
+
+        let origins: Vec<SparsePosition> = tiles
+            .positions()
+            .filter(|p| (p.x - min.x) % size == 0 && (p.y - min.y) % size == 0)
+            .collect();
+        let filled = |origin: SparsePosition| {
+            (0..size).all(|y| (0..size).all(|x| tiles.contains(origin + Point2::new(x, y))))
+        };
+        if origins.len() != 6 || !origins.iter().all(|&origin| filled(origin)) {
+            return Err(ParseError::missing(
+                map,
+                format!("six faces of {} by {} tiles for the cube", size, size),
+            ));
+        }
+
+        // unfolding is undone one edge at a time, starting from any face
+        let mut faces: Vec<Option<Face>> = vec![None; 6];
+        faces[0] = Some(Face {
+            origin: origins[0],
+            right: Point3::new(1, 0, 0),
+            down: Point3::new(0, 1, 0),
+            normal: Point3::new(0, 0, 1),
+        });
+        let mut queue = VecDeque::from([0]);
+        while let Some(i) = queue.pop_front() {
+            let face = faces[i].expect("queued faces are folded");
+            for direction in Direction4::ALL {
+                let next = face.origin + direction.offset() * size;
+                let Some(j) = origins.iter().position(|&origin| origin == next) else {
+                    continue;
+                };
+                if faces[j].is_none() {
+                    faces[j] = Some(face.fold(direction, next));
+                    queue.push_back(j);
+                }
+            }
+        }
+        let faces: Vec<Face> = faces.into_iter().flatten().collect();
+        let folds =
+            faces.len() == 6 && (0..6).all(|i| (0..i).all(|j| faces[i].normal != faces[j].normal));
+        if !folds {
+            return Err(ParseError::missing(map, "faces that fold into a cube"));
+        }
+
+        let layout: Vec<_> = origins
+            .iter()
+            .map(|&origin| Point2::new((origin.x - min.x) / size, (origin.y - min.y) / size))
+            .collect();
+        let shape = canonical(&layout);
+        let net = NETS
+            .iter()
+            .position(|net| canonical(&net_faces(net)) == shape)
+            .expect("everything that folds into a cube is one of the nets");
+
+        Ok(Self { size, faces, net })
+    }
+
+    /// How many tiles wide each face is.
+    pub fn face_size(&self) -> i64 {
+        self.size
+    }
+
+    /// Which of [`NETS`] the board is.
+    pub fn net(&self) -> usize {
+        self.net
+    }
+
+    fn face_at(&self, position: SparsePosition) -> &Face {
+        self.faces
+            .iter()
+            .find(|face| {
+                (0..self.size).contains(&(position.x - face.origin.x))
+                    && (0..self.size).contains(&(position.y - face.origin.y))
+            })
+            .expect("the position is on the cube")
+    }
+
+    /// Where walking off the board from `position` facing `direction` comes back onto it, and
+    /// which way it's facing then.
+    pub fn wrap(
+        &self,
+        position: SparsePosition,
+        direction: Direction4,
+    ) -> (SparsePosition, Direction4) {
+        let face = self.face_at(position);
+        let offset = along_edge(position - face.origin, direction, self.size);
+        let towards = face.towards(direction);
+        let next = self
+            .faces
+            .iter()
+            .find(|next| next.normal == towards)
+            .expect("every way out of a face leads to another");
+        let edge = Direction4::ALL
+            .into_iter()
+            .find(|&edge| next.towards(edge) == face.normal)
+            .expect("the faces share an edge");
+        let position = next.origin + on_edge(self.size - 1 - offset, edge, self.size);
+        (position, edge.opposite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A map of empty faces of `size` tiles laid out like `net`.
+    fn map(net: &str, size: usize) -> String {
+        net.lines()
+            .flat_map(|line| {
+                let row: String = line
+                    .chars()
+                    .map(|c| if c == '#' { "." } else { " " }.repeat(size))
+                    .collect();
+                vec![row; size]
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn fold(map: &str) -> Result<Cube, ParseError> {
+        let tiles = SparseGrid::parse(map, "a tile", |c| match c {
+            '.' | '#' => Some(Some(())),
+            ' ' => Some(None),
+            _ => None,
+        })?;
+        Cube::fold(map, &tiles)
+    }
+
+    #[test]
+    fn test_nets() {
+        for (i, net) in NETS.iter().enumerate() {
+            let cube = fold(&map(net, 3)).unwrap();
+            assert_eq!(cube.face_size(), 3);
+            assert_eq!(cube.net(), i);
+
+            // walking straight on goes around the cube and back to the start
+            for face in &cube.faces {
+                for direction in Direction4::ALL {
+                    let start = face.origin + Point2::new(1, 2);
+                    let (mut position, mut facing) = (start, direction);
+                    for _ in 0..4 * cube.size {
+                        let next = position + facing.offset();
+                        if cube.faces.iter().any(|f| {
+                            (0..cube.size).contains(&(next.x - f.origin.x))
+                                && (0..cube.size).contains(&(next.y - f.origin.y))
+                        }) {
+                            position = next;
+                        } else {
+                            (position, facing) = cube.wrap(position, facing);
+                        }
+                    }
+                    assert_eq!((position, facing), (start, direction), "net {}", i);
+                }
+            }
+        }
+
+        // turned and flipped over, it's still the same net
+        let cube = fold(&map("...#\n####\n...#", 2)).unwrap();
+        assert_eq!(cube.net(), 0);
+        let cube = fold(&map("..##\n.##.\n##..", 2)).unwrap();
+        assert_eq!(cube.net(), 9);
+    }
+
+    #[test]
+    fn test_sample() {
+        let (map, _) = include_str!("../data/sample.txt")
+            .split_once("\n\n")
+            .unwrap();
+        let cube = fold(map).unwrap();
+        assert_eq!(cube.face_size(), 4);
+        assert_eq!(cube.net(), 6);
+        // from A to B in the puzzle
+        assert_eq!(
+            cube.wrap(Point2::new(11, 5), Direction4::Right),
+            (Point2::new(14, 8), Direction4::Down)
+        );
+        // from C to D
+        assert_eq!(
+            cube.wrap(Point2::new(10, 11), Direction4::Down),
+            (Point2::new(1, 7), Direction4::Up)
+        );
+    }
+
+    #[test]
+    fn test_not_a_cube() {
+        let err = fold(&map("####\n##..", 2)).unwrap_err();
+        assert_eq!(err.expected, "faces that fold into a cube");
+        let err = fold(&map("###\n###", 2)).unwrap_err();
+        assert_eq!(err.expected, "faces that fold into a cube");
+        let err = fold("...\n.").unwrap_err();
+        assert_eq!(
+            err.expected,
+            "six square faces of the same size for the cube"
+        );
+        let err = fold(&(map("#...\n####\n#...", 2) + "\n.")).unwrap_err();
+        assert_eq!(
+            err.expected,
+            "six square faces of the same size for the cube"
+        );
+    }
+}
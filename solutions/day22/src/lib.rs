@@ -0,0 +1,520 @@
+use common::{
+    grid::SparsePosition, parse_number, Answer, Direction4, Explanation, ParseError, Point2,
+    Solution, SparseGrid,
+};
+use std::{cell::OnceCell, fmt::Display, str::FromStr};
+
+pub mod cube;
+pub mod trace;
+
+use cube::Cube;
+use trace::{Trace, Visit};
+
+#[derive(Clone, Copy, Debug)]
+enum Rotation {
+    L,
+    R,
+    N,
+}
+
+/// A turn on the path, and how far to walk after it. Turns are relative to wherever the explorer
+/// faces by then, which on the cube depends on the edges it walked over.
+#[derive(Clone, Copy, Debug)]
+struct Instruction {
+    rotation: Rotation,
+    steps: u8,
+}
+
+#[derive(Clone, Copy)]
+pub enum TeleportationStrategy {
+    /// Wrap around to the other end of the row or column.
+    Part1,
+    /// Fold the board into a cube and walk over its edges.
+    Part2,
+}
+
+impl TeleportationStrategy {
+    fn for_part(part: u8) -> Self {
+        match part {
+            1 => TeleportationStrategy::Part1,
+            _ => TeleportationStrategy::Part2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Movement {
+    U(u8),
+    D(u8),
+    L(u8),
+    R(u8),
+}
+
+impl From<Movement> for u32 {
+    fn from(value: Movement) -> Self {
+        match value {
+            Movement::U(_) => 3,
+            Movement::D(_) => 1,
+            Movement::L(_) => 2,
+            Movement::R(_) => 0,
+        }
+    }
+}
+
+impl Movement {
+    fn new(direction: Direction4, steps: u8) -> Self {
+        match direction {
+            Direction4::Up => Movement::U(steps),
+            Direction4::Down => Movement::D(steps),
+            Direction4::Left => Movement::L(steps),
+            Direction4::Right => Movement::R(steps),
+        }
+    }
+
+    /// How the puzzle draws the explorer facing this way.
+    fn arrow(&self) -> char {
+        match self {
+            Movement::U(_) => '^',
+            Movement::D(_) => 'v',
+            Movement::L(_) => '<',
+            Movement::R(_) => '>',
+        }
+    }
+
+    fn unpack_translation(&self) -> (u8, Direction4) {
+        match self {
+            Movement::U(steps) => (*steps, Direction4::Up),
+            Movement::D(steps) => (*steps, Direction4::Down),
+            Movement::L(steps) => (*steps, Direction4::Left),
+            Movement::R(steps) => (*steps, Direction4::Right),
+        }
+    }
+    fn new_with_rotation(last_movement: &Movement, rotation: Rotation, steps: u8) -> Self {
+        match (last_movement, rotation) {
+            (Movement::U(_), Rotation::L) => Movement::L(steps),
+            (Movement::U(_), Rotation::R) => Movement::R(steps),
+            (Movement::U(_), Rotation::N) => Movement::U(steps),
+            (Movement::D(_), Rotation::L) => Movement::R(steps),
+            (Movement::D(_), Rotation::R) => Movement::L(steps),
+            (Movement::D(_), Rotation::N) => Movement::D(steps),
+            (Movement::L(_), Rotation::L) => Movement::D(steps),
+            (Movement::L(_), Rotation::R) => Movement::U(steps),
+            (Movement::L(_), Rotation::N) => Movement::L(steps),
+            (Movement::R(_), Rotation::L) => Movement::U(steps),
+            (Movement::R(_), Rotation::R) => Movement::D(steps),
+            (Movement::R(_), Rotation::N) => Movement::R(steps),
+        }
+    }
+
+    pub fn translate_path_description(description: &str) -> Result<Vec<Instruction>, ParseError> {
+        if description.is_empty() {
+            return Err(ParseError::missing(description, "a path"));
+        }
+        let mut instructions = Vec::new();
+        let mut next_rotation = Rotation::N;
+        let mut rest = description;
+        while let Some(c) = rest.chars().next() {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            if digits == 0 {
+                let token = &rest[..c.len_utf8()];
+                return Err(ParseError::new(description, token, "a number of steps"));
+            }
+            let (steps, after) = rest.split_at(digits);
+            instructions.push(Instruction {
+                rotation: next_rotation,
+                steps: parse_number(description, steps)?,
+            });
+
+            let mut chars = after.chars();
+            next_rotation = match chars.next() {
+                Some('L') => Rotation::L,
+                Some('R') => Rotation::R,
+                None => Rotation::N,
+                Some(c) => {
+                    let token = &after[..c.len_utf8()];
+                    return Err(ParseError::new(description, token, "`L` or `R`"));
+                }
+            };
+            rest = chars.as_str();
+        }
+        Ok(instructions)
+    }
+}
+
+type Coordinate = Point2<u8>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Open,
+    Wall,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tile::Open => write!(f, "."),
+            Tile::Wall => write!(f, "#"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Board {
+    explorer: Coordinate,
+    /// Which way the explorer faces, with the steps it has left to walk.
+    movement: Movement,
+    tiles: SparseGrid<Tile>,
+    /// The board as the notes draw it, for pointing at what keeps it from folding into a cube.
+    map: String,
+    /// The board folded into a cube, the first time walking over its edges needs it. Wrapping
+    /// around flat works on boards of any shape.
+    cube: OnceCell<Result<Cube, ParseError>>,
+    /// Every tile visited so far, if the board is recording them.
+    visits: Option<Vec<Visit>>,
+}
+
+impl Board {
+    pub fn explore(&mut self, instruction: Instruction, strategy: &TeleportationStrategy) -> u32 {
+        let Instruction { rotation, steps } = instruction;
+        self.movement = Movement::new_with_rotation(&self.movement, rotation, steps);
+        self.visit();
+
+        for _ in 0..steps {
+            let (_, direction) = self.movement.unpack_translation();
+            // walking over an edge only turns the explorer if there's no wall on the other side
+            let (new_position, new_movement) = match self.explorer.step(direction) {
+                Some(next) if self.tiles.contains(position(next)) => (next, self.movement),
+                _ => self.teleport(&self.movement, strategy),
+            };
+
+            if self.tiles.get(position(new_position)) == Some(&Tile::Wall) {
+                break;
+            }
+            self.explorer = new_position;
+            self.movement = new_movement;
+            self.visit();
+        }
+
+        let movement_score = u32::from(self.movement);
+        let column_score = 4 * (self.explorer.x as u32 + 1);
+        let row_score = 1000 * (self.explorer.y as u32 + 1);
+
+        movement_score + column_score + row_score
+    }
+
+    fn cube(&self) -> Result<&Cube, ParseError> {
+        self.cube
+            .get_or_init(|| Cube::fold(&self.map, &self.tiles))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Records the tiles visited from now on.
+    fn record(&mut self) {
+        self.visits = Some(Vec::new());
+        self.visit();
+    }
+
+    fn visit(&mut self) {
+        if let Some(visits) = &mut self.visits {
+            visits.push(Visit {
+                x: self.explorer.x,
+                y: self.explorer.y,
+                facing: self.movement.arrow(),
+            });
+        }
+    }
+
+    /// The board as the notes draw it, a line per row.
+    fn draw(&self) -> Vec<String> {
+        let Some((min, max)) = self.tiles.bounds() else {
+            return Vec::new();
+        };
+        (min.y..=max.y)
+            .map(|y| {
+                let end = self.tiles.row(y).map(|(x, _)| x).max().unwrap_or(-1);
+                (0..=end)
+                    .map(|x| match self.tiles.get(Point2::new(x, y)) {
+                        Some(tile) => tile.to_string(),
+                        None => " ".to_string(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Where walking off the board with `movement` comes back onto it, and how it goes on.
+    fn teleport(
+        &self,
+        movement: &Movement,
+        strategy: &TeleportationStrategy,
+    ) -> (Coordinate, Movement) {
+        match strategy {
+            TeleportationStrategy::Part1 => self.teleport_part1(movement),
+            TeleportationStrategy::Part2 => self.teleport_part2(movement),
+        }
+    }
+
+    fn teleport_part1(&self, movement: &Movement) -> (Coordinate, Movement) {
+        let Point2 { x, y } = position(self.explorer);
+        let column = self.tiles.column(x).map(|(y, _)| y as u8);
+        let row = self.tiles.row(y).map(|(x, _)| x as u8);
+        // the explorer is on the board, so its row and column aren't empty
+        match &movement {
+            Movement::U(_) => (
+                Coordinate::new(self.explorer.x, column.max().unwrap()),
+                *movement,
+            ),
+            Movement::D(_) => (
+                Coordinate::new(self.explorer.x, column.min().unwrap()),
+                *movement,
+            ),
+            Movement::L(_) => (
+                Coordinate::new(row.max().unwrap(), self.explorer.y),
+                *movement,
+            ),
+            Movement::R(_) => (
+                Coordinate::new(row.min().unwrap(), self.explorer.y),
+                *movement,
+            ),
+        }
+    }
+
+    fn teleport_part2(&self, movement: &Movement) -> (Coordinate, Movement) {
+        let (steps, direction) = movement.unpack_translation();
+        let cube = self.cube().expect("walking on the cube folds it first");
+        let (next, direction) = cube.wrap(position(self.explorer), direction);
+        (
+            Coordinate::new(next.x as u8, next.y as u8),
+            Movement::new(direction, steps),
+        )
+    }
+}
+
+impl FromStr for Board {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = SparseGrid::parse(s, "an open tile, a wall or a space", |c| match c {
+            '.' => Some(Some(Tile::Open)),
+            '#' => Some(Some(Tile::Wall)),
+            ' ' => Some(None),
+            _ => None,
+        })?;
+
+        let first_row = s.lines().next().unwrap_or_default();
+        let explorer_x = tiles
+            .row(0)
+            .filter(|&(_, &tile)| tile == Tile::Open)
+            .map(|(x, _)| x as u8)
+            .min()
+            .ok_or_else(|| ParseError::missing(first_row, "an open tile in the top row"))?;
+        let explorer = Coordinate::new(explorer_x, 0);
+
+        Ok(Self {
+            explorer,
+            // start facing right
+            movement: Movement::R(0),
+            tiles,
+            map: s.to_string(),
+            cube: OnceCell::new(),
+            visits: None,
+        })
+    }
+}
+
+/// Where `coordinate` is on the board's tiles.
+fn position(coordinate: Coordinate) -> SparsePosition {
+    Point2::new(coordinate.x as i64, coordinate.y as i64)
+}
+
+/// The board and the path to follow on it.
+#[derive(Clone)]
+pub struct Notes {
+    board: Board,
+    path: Vec<Instruction>,
+}
+
+fn parse_notes(input: &str) -> Result<Notes, ParseError> {
+    let (board_str, movements_str) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::missing(input, "a blank line followed by the path"))?;
+    let path = movements_str.trim();
+    Ok(Notes {
+        board: board_str.parse()?,
+        path: Movement::translate_path_description(path).map_err(|e| e.within(input, path))?,
+    })
+}
+
+#[derive(Default)]
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = Notes;
+
+    fn parse(&self, input: &str) -> Result<Notes, ParseError> {
+        parse_notes(input)
+    }
+
+    /// Part 2 walks on the cube, so the board has to fold into one.
+    fn check(&self, part: u8, notes: &Notes) -> Result<(), ParseError> {
+        match TeleportationStrategy::for_part(part) {
+            TeleportationStrategy::Part1 => Ok(()),
+            TeleportationStrategy::Part2 => notes.board.cube().map(|_| ()),
+        }
+    }
+
+    // exploring moves the explorer, so each part works on its own copy of the board
+    fn problem1(&self, notes: &Notes) -> Answer {
+        password(notes.clone(), TeleportationStrategy::Part1).into()
+    }
+
+    fn problem2(&self, notes: &Notes) -> Answer {
+        password(notes.clone(), TeleportationStrategy::Part2).into()
+    }
+
+    /// The path drawn on the map, and every tile visited as a [`Trace`] in JSON to replay it.
+    fn explain(&self, part: u8, notes: &Notes) -> Option<Explanation> {
+        let trace = trace(notes.clone(), TeleportationStrategy::for_part(part)).ok()?;
+        Some(Explanation {
+            text: trace.render(),
+            json: Some(trace.to_json()),
+        })
+    }
+}
+
+/// Follows the path, returning the password and the board the explorer ended up on. Fails if
+/// the path is to be walked on a cube the board doesn't fold into.
+fn walk(
+    notes: Notes,
+    strategy: TeleportationStrategy,
+    record: bool,
+) -> Result<(u32, Board), ParseError> {
+    let Notes { mut board, path } = notes;
+    if let TeleportationStrategy::Part2 = strategy {
+        board.cube()?;
+    }
+    if record {
+        board.record();
+    }
+
+    let mut score = 0;
+    for instruction in path {
+        score = board.explore(instruction, &strategy);
+    }
+
+    Ok((score, board))
+}
+
+fn solve(notes: Notes, strategy: TeleportationStrategy) -> Result<u32, ParseError> {
+    Ok(walk(notes, strategy, false)?.0)
+}
+
+/// Every tile the explorer visits following the notes.
+pub fn trace(notes: Notes, strategy: TeleportationStrategy) -> Result<Trace, ParseError> {
+    let (_, board) = walk(notes, strategy, true)?;
+    Ok(Trace {
+        map: board.draw(),
+        visits: board.visits.unwrap_or_default(),
+    })
+}
+
+/// The password for a part that [`Day22::check`] has said the board suits.
+fn password(notes: Notes, strategy: TeleportationStrategy) -> u32 {
+    solve(notes, strategy).expect("checking the part folds the board if it needs to")
+}
+
+pub fn problem1(input: &str) -> u32 {
+    solve(parse_notes(input).unwrap(), TeleportationStrategy::Part1).unwrap()
+}
+
+pub fn problem2(input: &str) -> u32 {
+    solve(parse_notes(input).unwrap(), TeleportationStrategy::Part2).unwrap()
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 6032);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 5031);
+}
+
+#[test]
+fn test_flat_board() {
+    // wrapping around flat works on boards that don't fold into a cube
+    let notes = parse_notes("...#\n....\n\n5R1L2").unwrap();
+    assert_eq!(Day22.solve_parsed(1, &notes), Ok(Answer::from(2004_u32)));
+    assert_eq!(
+        Day22.solve_parsed(2, &notes).unwrap_err().to_string(),
+        "day 22, line 2, column 5: expected six square faces of the same size for the cube, \
+         found the end of the line"
+    );
+}
+
+#[test]
+fn test_trace() {
+    let notes = parse_notes(include_str!("../data/sample.txt")).unwrap();
+    let walked = trace(notes.clone(), TeleportationStrategy::Part1).unwrap();
+    // the picture in the puzzle
+    let expected = [
+        "        >>v#",
+        "        .#v.",
+        "        #.v.",
+        "        ..v.",
+        "...#...v..v#",
+        ">>>v...>#.>>",
+        "..#v...#....",
+        "...>>>>v..#.",
+        "        ...#....",
+        "        .....#..",
+        "        .#......",
+        "        ......#.",
+    ];
+    assert_eq!(walked.render(), expected.join("\n"));
+    assert_eq!(
+        walked.visits[0],
+        Visit {
+            x: 8,
+            y: 0,
+            facing: '>'
+        }
+    );
+    assert_eq!(
+        walked.visits.last(),
+        Some(&Visit {
+            x: 7,
+            y: 5,
+            facing: '>'
+        })
+    );
+    assert_eq!(Trace::from_json(&walked.to_json()).unwrap(), walked);
+    assert_eq!(walked.render_first(0), notes.board.draw().join("\n"));
+    assert_eq!(
+        Day22.explain(1, &notes),
+        Some(Explanation {
+            text: expected.join("\n"),
+            json: Some(walked.to_json())
+        })
+    );
+
+    let walked = trace(notes, TeleportationStrategy::Part2).unwrap();
+    assert_eq!(
+        walked.visits.last(),
+        Some(&Visit {
+            x: 6,
+            y: 4,
+            facing: '^'
+        })
+    );
+}
@@ -0,0 +1,53 @@
+//! The path the explorer walked, for drawing it the way the puzzle does and replaying it.
+
+use serde::{Deserialize, Serialize};
+
+/// A tile the explorer stood on, and which way it faced there as one of `>v<^`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Visit {
+    pub x: u8,
+    pub y: u8,
+    pub facing: char,
+}
+
+/// The board and every tile visited on it, in order. Turning on the spot visits the tile
+/// again.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trace {
+    /// The board as the notes draw it, a line per row.
+    pub map: Vec<String>,
+    pub visits: Vec<Visit>,
+}
+
+impl Trace {
+    /// The map with the path drawn on it, each tile showing the way the explorer last faced
+    /// there.
+    pub fn render(&self) -> String {
+        self.render_first(self.visits.len())
+    }
+
+    /// The map with only the first `visits` of the path drawn, to replay it a step at a time.
+    pub fn render_first(&self, visits: usize) -> String {
+        let mut rows: Vec<Vec<char>> = self.map.iter().map(|row| row.chars().collect()).collect();
+        for visit in self.visits.iter().take(visits) {
+            if let Some(tile) = rows
+                .get_mut(visit.y as usize)
+                .and_then(|row| row.get_mut(visit.x as usize))
+            {
+                *tile = visit.facing;
+            }
+        }
+        rows.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("traces are plain data")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
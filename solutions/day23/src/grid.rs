@@ -1,55 +1,62 @@
-use std::{
-    collections::{HashMap, HashSet},
-    str::FromStr,
-};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
 
-use crate::coordinate::{Coordinate, Direction};
+use common::{grid::SparsePosition, Direction8, ParseError, SparseGrid};
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Elf;
+
+impl Display for Elf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#")
+    }
+}
+
+#[derive(Clone)]
 pub struct Grid {
-    elves: HashSet<Coordinate>,
+    elves: SparseGrid<Elf>,
 }
 
 impl Grid {
-    fn propose_elf(&self, elf: &Coordinate, round: usize) -> Option<Coordinate> {
-        let n_translation = elf.translate_new(Direction::N);
-        let e_translation = elf.translate_new(Direction::E);
-        let s_translation = elf.translate_new(Direction::S);
-        let w_translation = elf.translate_new(Direction::W);
-        let ne_translation = elf.translate_new(Direction::NE);
-        let nw_translation = elf.translate_new(Direction::NW);
-        let se_translation = elf.translate_new(Direction::SE);
-        let sw_translation = elf.translate_new(Direction::SW);
-
-        let mut proposals: [Option<Coordinate>; 4] = [None; 4];
+    fn propose_elf(&self, elf: SparsePosition, round: usize) -> Option<SparsePosition> {
+        let n_translation = elf + Direction8::N.offset();
+        let e_translation = elf + Direction8::E.offset();
+        let s_translation = elf + Direction8::S.offset();
+        let w_translation = elf + Direction8::W.offset();
+        let ne_translation = elf + Direction8::NE.offset();
+        let nw_translation = elf + Direction8::NW.offset();
+        let se_translation = elf + Direction8::SE.offset();
+        let sw_translation = elf + Direction8::SW.offset();
+
+        let mut proposals: [Option<SparsePosition>; 4] = [None; 4];
         let mut num_proposals = 0;
 
-        if !self.elves.contains(&n_translation)
-            && !self.elves.contains(&ne_translation)
-            && !self.elves.contains(&nw_translation)
+        if !self.elves.contains(n_translation)
+            && !self.elves.contains(ne_translation)
+            && !self.elves.contains(nw_translation)
         {
             proposals[0] = Some(n_translation);
             num_proposals += 1;
         }
 
-        if !self.elves.contains(&s_translation)
-            && !self.elves.contains(&se_translation)
-            && !self.elves.contains(&sw_translation)
+        if !self.elves.contains(s_translation)
+            && !self.elves.contains(se_translation)
+            && !self.elves.contains(sw_translation)
         {
             proposals[1] = Some(s_translation);
             num_proposals += 1;
         }
 
-        if !self.elves.contains(&w_translation)
-            && !self.elves.contains(&sw_translation)
-            && !self.elves.contains(&nw_translation)
+        if !self.elves.contains(w_translation)
+            && !self.elves.contains(sw_translation)
+            && !self.elves.contains(nw_translation)
         {
             proposals[2] = Some(w_translation);
             num_proposals += 1;
         }
 
-        if !self.elves.contains(&e_translation)
-            && !self.elves.contains(&se_translation)
-            && !self.elves.contains(&ne_translation)
+        if !self.elves.contains(e_translation)
+            && !self.elves.contains(se_translation)
+            && !self.elves.contains(ne_translation)
         {
             proposals[3] = Some(e_translation);
             num_proposals += 1;
@@ -72,12 +79,9 @@ impl Grid {
     fn play_round(&mut self, round: usize) -> bool {
         let mut moves = HashMap::new();
 
-        for elf in self.elves.iter() {
+        for elf in self.elves.positions() {
             if let Some(new_position) = self.propose_elf(elf, round) {
-                moves
-                    .entry(new_position)
-                    .or_insert(Vec::new())
-                    .push(elf.clone());
+                moves.entry(new_position).or_insert(Vec::new()).push(elf);
             }
         }
 
@@ -93,8 +97,8 @@ impl Grid {
                     }
                 })
         {
-            self.elves.remove(&old_position);
-            self.elves.insert(new_position);
+            self.elves.remove(old_position);
+            self.elves.insert(new_position, Elf);
             changed = true;
         }
         changed
@@ -107,13 +111,10 @@ impl Grid {
             }
         }
 
-        let min_x = self.elves.iter().map(|e| e.x).min().unwrap_or(0);
-        let max_x = self.elves.iter().map(|e| e.x).max().unwrap_or(0);
-        let min_y = self.elves.iter().map(|e| e.y).min().unwrap_or(0);
-        let max_y = self.elves.iter().map(|e| e.y).max().unwrap_or(0);
-        let num_elves = self.elves.len() as i16;
+        let (min, max) = self.elves.bounds().unwrap_or_default();
+        let num_elves = self.elves.len() as i64;
 
-        (((max_x - min_x + 1) * (max_y - min_y + 1)) - num_elves) as u32
+        (((max.x - min.x + 1) * (max.y - min.y + 1)) - num_elves) as u32
     }
 
     pub fn num_rounds_until_no_movement(&mut self) -> usize {
@@ -127,22 +128,14 @@ impl Grid {
 }
 
 impl FromStr for Grid {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let elves: HashSet<Coordinate> = s
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars().enumerate().filter_map(move |(x, c)| {
-                    if c == '#' {
-                        Some(Coordinate::new(x as i16, y as i16))
-                    } else {
-                        None
-                    }
-                })
-            })
-            .collect();
+        let elves = SparseGrid::parse(s, "`#` or `.`", |c| match c {
+            '#' => Some(Some(Elf)),
+            '.' => Some(None),
+            _ => None,
+        })?;
 
         Ok(Self { elves })
     }
@@ -0,0 +1,60 @@
+pub mod grid;
+
+use crate::grid::Grid;
+use common::{Answer, ParseError, Solution};
+
+#[derive(Default)]
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Grid;
+
+    fn parse(&self, input: &str) -> Result<Grid, ParseError> {
+        input.parse()
+    }
+
+    // the simulation moves the elves, so each part works on its own copy
+    fn problem1(&self, grid: &Grid) -> Answer {
+        grid.clone().compute_ground_tiles(NUM_ROUNDS).into()
+    }
+
+    fn problem2(&self, grid: &Grid) -> Answer {
+        grid.clone().num_rounds_until_no_movement().into()
+    }
+}
+
+const NUM_ROUNDS: usize = 10;
+
+pub fn problem1(input: &str) -> u32 {
+    input
+        .parse::<Grid>()
+        .unwrap()
+        .compute_ground_tiles(NUM_ROUNDS)
+}
+
+pub fn problem2(input: &str) -> usize {
+    input
+        .parse::<Grid>()
+        .unwrap()
+        .num_rounds_until_no_movement()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_problem1() {
+        let input = include_str!("../data/sample.txt");
+        let res = problem1(input);
+        assert_eq!(res, 110);
+    }
+
+    #[test]
+    fn test_problem2() {
+        let input = include_str!("../data/sample.txt");
+        let res = problem2(input);
+        assert_eq!(res, 20);
+    }
+}
@@ -1,27 +1,34 @@
 use std::collections::HashSet;
 
-use crate::{coordinate::Coordinate, direction::Direction};
+use common::{Direction4, Point2};
 
+use crate::grid::Coordinate;
+
+/// Every blizzard blowing in one direction, by where they were at the start.
 #[derive(Clone, Debug)]
 pub struct Blizzard {
     winds: HashSet<Coordinate>,
-    direction: Direction,
+    direction: Direction4,
 }
 
 impl Blizzard {
-    pub fn new(winds: HashSet<Coordinate>, direction: Direction) -> Self {
+    pub fn new(winds: HashSet<Coordinate>, direction: Direction4) -> Self {
         Self { winds, direction }
     }
 
-    pub fn blow_winds(&mut self, max_x: u8, max_y: u8) {
-        self.winds = self
-            .winds
-            .iter()
-            .map(|c| c.blow(max_x, max_y, self.direction))
-            .collect();
-    }
-
-    pub fn contains(&self, coordinate: &Coordinate) -> bool {
-        self.winds.contains(coordinate)
+    /// Whether one of the blizzards is at `coordinate` after `time` minutes. Blizzards wrap
+    /// around inside the walls at `max_x` and `max_y`, so this looks back to where a blizzard
+    /// would have had to start instead of moving every blizzard.
+    pub fn covers(&self, coordinate: Coordinate, time: usize, max_x: u8, max_y: u8) -> bool {
+        let offset: Point2<i64> = self.direction.offset();
+        let unwind = |position: u8, offset: i64, max: u8| {
+            let inner = max as i64 - 1;
+            ((position as i64 - 1 - offset * (time as i64 % inner)).rem_euclid(inner) + 1) as u8
+        };
+        let origin = Point2::new(
+            unwind(coordinate.x, offset.x, max_x),
+            unwind(coordinate.y, offset.y, max_y),
+        );
+        self.winds.contains(&origin)
     }
 }
@@ -0,0 +1,87 @@
+pub mod blizzard;
+pub mod grid;
+
+use common::{Answer, Direction4, Explanation, ParseError, Solution};
+use grid::{Coordinate, Grid};
+
+#[derive(Default)]
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input = Grid;
+
+    fn parse(&self, input: &str) -> Result<Grid, ParseError> {
+        input.parse()
+    }
+
+    fn problem1(&self, grid: &Grid) -> Answer {
+        grid.find_fastest_exit().into()
+    }
+
+    fn problem2(&self, grid: &Grid) -> Answer {
+        grid.round_trip_for_snacks().into()
+    }
+
+    /// The route the part found, minute by minute.
+    fn explain(&self, part: u8, grid: &Grid) -> Option<Explanation> {
+        let route = match part {
+            1 => grid.exit_route(),
+            _ => grid.snack_route(),
+        };
+        Some(Explanation {
+            text: describe_route(&route),
+            json: None,
+        })
+    }
+}
+
+pub fn problem1(input: &str) -> u32 {
+    input.parse::<Grid>().unwrap().find_fastest_exit() as u32
+}
+
+pub fn problem2(input: &str) -> u32 {
+    input.parse::<Grid>().unwrap().round_trip_for_snacks() as u32
+}
+
+/// Describes each minute of a route the way the puzzle does, like `Minute 3, wait.`
+pub fn describe_route(route: &[Coordinate]) -> String {
+    let mut description = Vec::new();
+    for (minute, step) in route.windows(2).enumerate() {
+        let action = match Direction4::ALL
+            .into_iter()
+            .find(|&d| step[0].step(d) == Some(step[1]))
+        {
+            Some(Direction4::Up) => "move up",
+            Some(Direction4::Right) => "move right",
+            Some(Direction4::Down) => "move down",
+            Some(Direction4::Left) => "move left",
+            None => "wait",
+        };
+        description.push(format!("Minute {}, {}.", minute + 1, action));
+    }
+    description.join("\n")
+}
+
+#[test]
+fn test_problem1() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem1(input);
+    assert_eq!(res, 18);
+}
+
+#[test]
+fn test_problem2() {
+    let input = include_str!("../data/sample.txt");
+    let res = problem2(input);
+    assert_eq!(res, 54);
+}
+
+#[test]
+fn test_route() {
+    let grid: Grid = include_str!("../data/sample.txt").parse().unwrap();
+    let route = Day24.explain(2, &grid).unwrap().text;
+    assert_eq!(route.lines().count(), 54);
+    assert_eq!(route.lines().last(), Some("Minute 54, move down."));
+}
@@ -0,0 +1,48 @@
+use common::{parse_lines, Answer, ParseError, Solution};
+use snafu::Snafu;
+
+pub mod snafu;
+
+#[derive(Default)]
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const PARTS: u8 = 1;
+
+    type Input = Vec<Snafu>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Snafu>, ParseError> {
+        parse_lines(input, str::parse)
+    }
+
+    fn problem1(&self, numbers: &Vec<Snafu>) -> Answer {
+        numbers.iter().cloned().sum::<Snafu>().to_string().into()
+    }
+
+    // there is no puzzle for part 2 - the last star is awarded for finishing the other 49
+    fn problem2(&self, _numbers: &Vec<Snafu>) -> Answer {
+        unreachable!("Day 25 only has one part")
+    }
+}
+
+pub fn problem1(input: &str) -> String {
+    Day25
+        .parse(input)
+        .unwrap()
+        .into_iter()
+        .sum::<Snafu>()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem1() {
+        let input = include_str!("../data/sample.txt");
+        let res = problem1(input);
+        assert_eq!(res, "2=-1=0".to_string());
+    }
+}
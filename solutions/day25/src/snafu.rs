@@ -1,140 +1,15 @@
-use std::{iter::Sum, ops::Add, str::FromStr};
+use common::{balanced::Alphabet, Balanced};
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum SnafuSymbol {
-    Two,
-    One,
-    Zero,
-    Minus,
-    DoubleMinus,
-}
-
-impl From<SnafuSymbol> for isize {
-    fn from(value: SnafuSymbol) -> Self {
-        Self::from(&value)
-    }
-}
-
-impl From<&SnafuSymbol> for isize {
-    fn from(value: &SnafuSymbol) -> Self {
-        match value {
-            &SnafuSymbol::Two => 2,
-            &SnafuSymbol::One => 1,
-            &SnafuSymbol::Zero => 0,
-            &SnafuSymbol::Minus => -1,
-            &SnafuSymbol::DoubleMinus => -2,
-        }
-    }
-}
-
-impl From<char> for SnafuSymbol {
-    fn from(value: char) -> Self {
-        match value {
-            '2' => Self::Two,
-            '1' => Self::One,
-            '0' => Self::Zero,
-            '-' => Self::Minus,
-            '=' => Self::DoubleMinus,
-            _ => unreachable!(),
-        }
-    }
-}
-
-impl From<SnafuSymbol> for char {
-    fn from(value: SnafuSymbol) -> Self {
-        Self::from(&value)
-    }
-}
-
-impl From<&SnafuSymbol> for char {
-    fn from(value: &SnafuSymbol) -> Self {
-        match value {
-            SnafuSymbol::Two => '2',
-            SnafuSymbol::One => '1',
-            SnafuSymbol::Zero => '0',
-            SnafuSymbol::Minus => '-',
-            SnafuSymbol::DoubleMinus => '=',
-        }
-    }
-}
-
-pub struct Snafu {
-    /// most significant appears first
-    symbols: Vec<SnafuSymbol>,
-}
-
-impl Add for Snafu {
-    type Output = Self;
+/// The digits of SNAFU numbers: balanced base 5, with `=` for -2 and `-` for -1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SnafuDigits;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self::from(isize::from(self) + isize::from(rhs))
-    }
-}
-
-impl Sum for Snafu {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.reduce(|acc, s| acc + s).unwrap()
-    }
-}
-
-impl FromStr for Snafu {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            symbols: s.chars().into_iter().map(|c| c.into()).collect(),
-        })
-    }
+impl Alphabet for SnafuDigits {
+    const NAME: &'static str = "SNAFU";
+    const SYMBOLS: &'static [char] = &['=', '-', '0', '1', '2'];
 }
 
-impl ToString for Snafu {
-    fn to_string(&self) -> String {
-        self.symbols.iter().map(|s| char::from(s)).collect()
-    }
-}
-
-impl From<Snafu> for isize {
-    fn from(value: Snafu) -> Self {
-        value
-            .symbols
-            .iter()
-            .rev()
-            .enumerate()
-            .map(|(i, s)| 5_isize.pow(i as u32) * isize::from(s))
-            .sum()
-    }
-}
-
-impl From<isize> for Snafu {
-    fn from(value: isize) -> Self {
-        let mut value_remaining = value;
-        let mut symbols: Vec<SnafuSymbol> = Vec::new();
-
-        while value_remaining > 0 {
-            let r = value_remaining % 5;
-            value_remaining = value_remaining / 5;
-            let s = match r {
-                0 => SnafuSymbol::Zero,
-                1 => SnafuSymbol::One,
-                2 => SnafuSymbol::Two,
-                3 => {
-                    value_remaining += 1;
-                    SnafuSymbol::DoubleMinus
-                }
-                4 => {
-                    value_remaining += 1;
-                    SnafuSymbol::Minus
-                }
-                _ => unreachable!(),
-            };
-            symbols.push(s)
-        }
-
-        symbols.reverse();
-
-        Self { symbols }
-    }
-}
+pub type Snafu = Balanced<SnafuDigits>;
 
 #[cfg(test)]
 mod tests {
@@ -145,7 +20,7 @@ mod tests {
             #[test]
             fn $to_dec_name() {
                 let snafu: Snafu = $snafu_str.parse().unwrap();
-                assert_eq!($dec, isize::from(snafu));
+                assert_eq!(Ok($dec), i128::try_from(&snafu));
             }
 
             #[test]
@@ -1,14 +1,31 @@
-fn main() {
-    let input = include_str!("../data/input.txt");
-    println!("Problem 1: {}", problem1(input));
-    println!("Problem 2: {}", problem2(input));
+use common::{Answer, ParseError, Solution};
+
+#[derive(Default)]
+pub struct DayDAY_PADDED;
+
+impl Solution for DayDAY_PADDED {
+    const DAY: u8 = DAY_NUM;
+
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn problem1(&self, input: &String) -> Answer {
+        problem1(input).into()
+    }
+
+    fn problem2(&self, input: &String) -> Answer {
+        problem2(input).into()
+    }
 }
 
-fn problem1(input: &str) -> u32 {
+pub fn problem1(input: &str) -> PART1_TYPE {
     unimplemented!()
 }
 
-fn problem2(input: &str) -> u32 {
+pub fn problem2(input: &str) -> PART2_TYPE {
     unimplemented!()
 }
 
@@ -27,6 +44,6 @@ mod tests {
     fn test_problem2() {
         let input = include_str!("../data/sample.txt");
         let res = problem2(input);
-        assert_eq!(res, 0);
+        assert_eq!(res, PART2_SAMPLE_SOLUTION);
     }
 }